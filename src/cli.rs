@@ -0,0 +1,232 @@
+//! Kommandozeilen-Parser für das Hauptprogramm
+//!
+//! Überschreibt einzelne Felder von `SimConfig`:
+//! - Voreinstellungen `--fast` / `--test`
+//! - Werte `--dt`, `--max-time`, `--isp`, `--thrust`, `--seed`
+//! - Schalter `--quiet`
+//! - Missionsprofil `--config <file.toml>`
+//! - Telemetrie-Export `--telemetry-out <file>`
+
+use crate::simulation::SimConfig;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Hilfetext bei `--help` und fehlerhaften Argumenten
+pub const USAGE: &str = "\
+Usage: azb_raumschiff [OPTIONS]
+
+Options:
+  --fast                Schnelle Simulation (dt=5s)
+  --test                Kurzer Test (1 Stunde simuliert)
+  --dt <s>              Zeitschritt [s]
+  --max-time <s>        Maximale Simulationszeit [s]
+  --isp <s>             Spezifischer Impuls [s]
+  --thrust <N>          Maximaler Schub [N]
+  --seed <n>            Seed für Sensorrauschen
+  --quiet               Keine Statusmeldungen während der Simulation
+  --config <file.toml>  Missionsprofil laden
+  --telemetry-out <f>   Telemetrie nach Missionsende als Binärdatei schreiben
+  --help                Diese Hilfe anzeigen";
+
+/// Voreinstellung für die Basiskonfiguration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Preset {
+    /// Standardmission
+    Standard,
+    /// Größerer Zeitschritt
+    Fast,
+    /// Kurzer Testlauf
+    Test,
+}
+
+impl Preset {
+    /// Basiskonfiguration der Voreinstellung
+    pub fn config(self) -> SimConfig {
+        match self {
+            Preset::Standard => SimConfig::default(),
+            Preset::Fast => SimConfig {
+                dt: 5.0, // 5s Zeitschritt
+                telemetry_interval: 600.0,
+                ..Default::default()
+            },
+            Preset::Test => SimConfig {
+                dt: 1.0,
+                max_time: 3600.0, // 1 Stunde
+                telemetry_interval: 60.0,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Fehler beim Parsen der Kommandozeile
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliError {
+    /// Unbekanntes Argument
+    UnknownFlag(String),
+    /// Option ohne Wert
+    MissingValue(String),
+    /// Wert nicht als Zahl lesbar
+    InvalidValue { flag: String, value: String },
+    /// `--help` angefordert
+    Help,
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::UnknownFlag(flag) => write!(f, "Unbekannte Option: {}", flag),
+            CliError::MissingValue(flag) => write!(f, "Fehlender Wert für {}", flag),
+            CliError::InvalidValue { flag, value } => {
+                write!(f, "Ungültiger Wert für {}: {}", flag, value)
+            }
+            CliError::Help => write!(f, "{}", USAGE),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Geparste Kommandozeile
+#[derive(Debug, Clone, PartialEq)]
+pub struct CliArgs {
+    pub preset: Preset,
+    /// Pfad zum Missionsprofil
+    pub config_file: Option<PathBuf>,
+    /// Zieldatei für die binäre Telemetrie
+    pub telemetry_out: Option<PathBuf>,
+    pub dt: Option<f64>,
+    pub max_time: Option<f64>,
+    pub isp: Option<f64>,
+    pub max_thrust: Option<f64>,
+    pub seed: Option<u64>,
+    pub quiet: bool,
+}
+
+impl CliArgs {
+    /// Parst Argumente (ohne Programmnamen)
+    pub fn parse<I, S>(args: I) -> Result<Self, CliError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut parsed = CliArgs {
+            preset: Preset::Standard,
+            config_file: None,
+            telemetry_out: None,
+            dt: None,
+            max_time: None,
+            isp: None,
+            max_thrust: None,
+            seed: None,
+            quiet: false,
+        };
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let flag = arg.as_ref();
+            let mut value = || {
+                args.next()
+                    .map(|v| v.as_ref().to_string())
+                    .ok_or_else(|| CliError::MissingValue(flag.to_string()))
+            };
+
+            match flag {
+                "--fast" => parsed.preset = Preset::Fast,
+                "--test" => parsed.preset = Preset::Test,
+                "--quiet" => parsed.quiet = true,
+                "--help" | "-h" => return Err(CliError::Help),
+                "--dt" => parsed.dt = Some(parse_number(flag, &value()?)?),
+                "--max-time" => parsed.max_time = Some(parse_number(flag, &value()?)?),
+                "--isp" => parsed.isp = Some(parse_number(flag, &value()?)?),
+                "--thrust" => parsed.max_thrust = Some(parse_number(flag, &value()?)?),
+                "--seed" => parsed.seed = Some(parse_number(flag, &value()?)?),
+                "--config" => parsed.config_file = Some(PathBuf::from(value()?)),
+                "--telemetry-out" => parsed.telemetry_out = Some(PathBuf::from(value()?)),
+                _ => return Err(CliError::UnknownFlag(flag.to_string())),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Überschreibt die Basiskonfiguration mit den angegebenen Werten
+    pub fn apply(&self, mut config: SimConfig) -> SimConfig {
+        if let Some(dt) = self.dt {
+            config.dt = dt;
+        }
+        if let Some(max_time) = self.max_time {
+            config.max_time = max_time;
+        }
+        if let Some(isp) = self.isp {
+            config.isp = isp;
+        }
+        if let Some(max_thrust) = self.max_thrust {
+            config.max_thrust = max_thrust;
+        }
+        if let Some(seed) = self.seed {
+            config.seed = seed;
+        }
+        if self.quiet {
+            config.quiet = true;
+        }
+        config
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, CliError> {
+    value.parse().map_err(|_| CliError::InvalidValue {
+        flag: flag.to_string(),
+        value: value.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_overrides() {
+        let args = CliArgs::parse(["--dt", "2.5", "--seed", "7"]).unwrap();
+        let config = args.apply(Preset::Standard.config());
+
+        assert_eq!(config.dt, 2.5);
+        assert_eq!(config.seed, 7);
+        // Nicht angegebene Felder bleiben unverändert
+        assert_eq!(config.isp, SimConfig::default().isp);
+        assert!(!config.quiet);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            CliArgs::parse(["--warp"]),
+            Err(CliError::UnknownFlag("--warp".to_string()))
+        );
+        assert_eq!(
+            CliArgs::parse(["--dt"]),
+            Err(CliError::MissingValue("--dt".to_string()))
+        );
+        assert!(matches!(
+            CliArgs::parse(["--seed", "-1"]),
+            Err(CliError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_preset_with_config_file() {
+        let args = CliArgs::parse([
+            "--test",
+            "--config",
+            "mission.toml",
+            "--quiet",
+            "--telemetry-out",
+            "tm.bin",
+        ])
+        .unwrap();
+        assert_eq!(args.preset, Preset::Test);
+        assert_eq!(args.config_file, Some(PathBuf::from("mission.toml")));
+        assert_eq!(args.telemetry_out, Some(PathBuf::from("tm.bin")));
+        assert!(args.apply(args.preset.config()).quiet);
+    }
+}
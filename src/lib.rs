@@ -6,9 +6,11 @@
 //! - fdir: Fault Detection, Isolation & Recovery
 //! - telemetry: Telemetrie & Datenhandling
 //! - simulation: 6-DOF Simulations-Loop
+//! - cli: Kommandozeilen-Parser für das Hauptprogramm
 
 pub mod physics;
 pub mod gnc;
 pub mod fdir;
 pub mod telemetry;
 pub mod simulation;
+pub mod cli;
@@ -5,9 +5,13 @@
 //! Usage:
 //!   cargo run              # Standardmission
 //!   cargo run -- --fast    # Schnelle Simulation (größerer Zeitschritt)
-//!   cargo run -- --test    # Kurzer Test (10 Minuten simuliert)
+//!   cargo run -- --test    # Kurzer Test (1 Stunde simuliert)
+//!   cargo run -- --dt 2.5 --seed 7 --quiet
+//!   cargo run -- --help    # Alle Optionen
 
-use azb_raumschiff::simulation::{MoonMissionSim, SimConfig, run_moon_mission};
+use azb_raumschiff::cli::{CliArgs, CliError, Preset, USAGE};
+use azb_raumschiff::simulation::{simulate_with_logger, SimConfig};
+use azb_raumschiff::telemetry::{MissionLogger, NullLogger, StdoutLogger};
 use std::env;
 
 fn main() {
@@ -17,35 +21,54 @@ fn main() {
     println!("╚══════════════════════════════════════════════════════════════╝");
     println!();
 
-    let args: Vec<String> = env::args().collect();
+    let args = match CliArgs::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(CliError::Help) => {
+            println!("{}", USAGE);
+            std::process::exit(0);
+        }
+        Err(err) => {
+            eprintln!("❌ {}", err);
+            eprintln!();
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
 
-    let result = if args.contains(&"--fast".to_string()) {
-        println!("⚡ Schnellmodus aktiviert (dt=5s)");
-        println!();
-        let config = SimConfig {
-            dt: 5.0,           // 5s Zeitschritt
-            telemetry_interval: 600.0,
-            ..Default::default()
-        };
-        let mut sim = MoonMissionSim::new(config);
-        sim.run()
-    } else if args.contains(&"--test".to_string()) {
-        println!("🧪 Testmodus (1 Stunde simuliert)");
-        println!();
-        let config = SimConfig {
-            dt: 1.0,
-            max_time: 3600.0,    // 1 Stunde
-            telemetry_interval: 60.0,
-            ..Default::default()
-        };
-        let mut sim = MoonMissionSim::new(config);
-        sim.run()
+    match args.preset {
+        Preset::Fast => println!("⚡ Schnellmodus aktiviert (dt=5s)"),
+        Preset::Test => println!("🧪 Testmodus (1 Stunde simuliert)"),
+        Preset::Standard => {
+            println!("🎯 Standardmission (kann einige Minuten dauern)");
+            println!("   Tipp: `cargo run -- --fast` für schnellere Simulation");
+        }
+    }
+    println!();
+
+    // Missionsprofil ersetzt die Voreinstellung, Optionen überschreiben beides
+    let base = match &args.config_file {
+        Some(path) => match SimConfig::from_toml_file(path) {
+            Ok(config) => {
+                println!("📄 Missionsprofil: {}", path.display());
+                println!();
+                config
+            }
+            Err(err) => {
+                eprintln!("❌ {}", err);
+                std::process::exit(2);
+            }
+        },
+        None => args.preset.config(),
+    };
+    let config = args.apply(base);
+
+    // Konsolenausgabe über StdoutLogger, die Bibliothek selbst bleibt still
+    let logger: Box<dyn MissionLogger> = if config.quiet {
+        Box::new(NullLogger)
     } else {
-        println!("🎯 Standardmission (kann einige Minuten dauern)");
-        println!("   Tipp: `cargo run -- --fast` für schnellere Simulation");
-        println!();
-        run_moon_mission()
+        Box::new(StdoutLogger)
     };
+    let result = simulate_with_logger(&config, logger);
 
     // Ergebnis ausgeben
     println!();
@@ -81,8 +104,17 @@ fn main() {
         final_vel.norm()
     );
 
+    println!();
+    print!("{}", result.timeline.export());
+
     println!();
     println!("Telemetrie:   {} Pakete aufgezeichnet", result.telemetry.get_packets().len());
+    if let Some(path) = &args.telemetry_out {
+        match result.telemetry.write_binary(path) {
+            Ok(()) => println!("              → {}", path.display()),
+            Err(err) => eprintln!("❌ Telemetrie nicht geschrieben ({}): {}", path.display(), err),
+        }
+    }
     println!("════════════════════════════════════════════════════════════════");
 
     // Exit-Code
@@ -5,7 +5,8 @@
 //! - r(t) = r0 + v0*t + 0.5*a*t²
 //! - ṁ = -T / (Isp * g0)
 
-use nalgebra::Vector3;
+use nalgebra::{Matrix3, Vector3, Vector6};
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// Gravitationskonstante [m³/(kg·s²)]
 pub const G: f64 = 6.67430e-11;
@@ -16,14 +17,277 @@ pub const M_EARTH: f64 = 5.972e24;
 /// Mondmasse [kg]
 pub const M_MOON: f64 = 7.342e22;
 
+/// Mittlerer Erdradius [m]
+pub const R_EARTH: f64 = 6.371e6;
+
+/// Mittlerer Mondradius [m]
+pub const R_MOON: f64 = 1.737e6;
+
 /// Erdbeschleunigung [m/s²]
 pub const G0: f64 = 9.80665;
 
 /// Erde-Mond Distanz [m] (mittlere)
 pub const EARTH_MOON_DISTANCE: f64 = 384_400_000.0;
 
+/// Radius der Einflusssphäre des Mondes [m] (Laplace: a * (m/M)^(2/5))
+pub const MOON_SOI_RADIUS: f64 = 66_100_000.0;
+
+/// Siderische Rotationsrate der Erde [rad/s]
+pub const EARTH_ROTATION_RATE: f64 = 7.292_115_9e-5;
+
+/// Astronomische Einheit [m]
+pub const AU: f64 = 1.495_978_707e11;
+
+/// Solarkonstante bei 1 AU [W/m²]
+pub const SOLAR_CONSTANT: f64 = 1361.0;
+
+/// Lichtgeschwindigkeit [m/s]
+pub const SPEED_OF_LIGHT: f64 = 299_792_458.0;
+
+/// Gravitationsparameter der Sonne [m³/s²]
+pub const MU_SUN: f64 = 1.327_124_400_18e20;
+
+/// Abplattungskoeffizient J2 der Erde
+pub const J2_EARTH: f64 = 1.082_63e-3;
+
+/// Luftdichte auf Meereshöhe [kg/m³]
+const SEA_LEVEL_DENSITY: f64 = 1.225;
+
+/// Luftdruck auf Meereshöhe [kPa]
+const SEA_LEVEL_PRESSURE: f64 = 101.325;
+
+/// Skalenhöhe der exponentiellen Erdatmosphäre [m]
+const SCALE_HEIGHT: f64 = 8_500.0;
+
+/// Siderisches Jahr [s]
+const SIDEREAL_YEAR: f64 = 365.256_363 * 86_400.0;
+
+/// Synodische Sonnenrotation (Modulation der kosmischen Strahlung) [s]
+const SOLAR_ROTATION: f64 = 27.0 * 86_400.0;
+
+/// Fahrzeugradius [m] (Zylindermodell)
+pub const VEHICLE_RADIUS: f64 = 2.0;
+
+/// Fahrzeuglänge [m] (Zylindermodell, Längsachse = Schubachse Z)
+pub const VEHICLE_LENGTH: f64 = 10.0;
+
+/// Länge des Treibstofftanks [m] (zentriert im Schwerpunkt)
+pub const TANK_LENGTH: f64 = 6.0;
+
+/// Behandlung der Gravitations-Singularität für r → 0
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GravitySoftening {
+    /// Keine Gravitation bis einschließlich des Radius [m] (sprunghaft)
+    Cutoff(f64),
+    /// Plummer-Glättung mit Länge ε [m]: a = μ r / (r² + ε²)^{3/2} (stetig)
+    Plummer(f64),
+}
+
+impl GravitySoftening {
+    /// Bisheriges Verhalten: Abschneiden unterhalb von 1 m
+    pub const DEFAULT: Self = GravitySoftening::Cutoff(1.0);
+
+    /// Beschleunigung in Richtung `r` (vom Raumschiff zum Körper) [m/s²]
+    pub fn acceleration(&self, r: &Vector3<f64>, mu: f64) -> Vector3<f64> {
+        let d2 = r.norm_squared();
+        match *self {
+            GravitySoftening::Cutoff(radius) => {
+                if d2 <= radius * radius {
+                    Vector3::zeros()
+                } else {
+                    r * (mu / (d2 * d2.sqrt()))
+                }
+            }
+            GravitySoftening::Plummer(epsilon) => {
+                let softened = d2 + epsilon * epsilon;
+                if softened == 0.0 {
+                    Vector3::zeros()
+                } else {
+                    r * (mu / (softened * softened.sqrt()))
+                }
+            }
+        }
+    }
+
+    /// Potential im Abstand `distance` [J/kg], passend zu `acceleration`
+    /// (Cutoff: 0 innerhalb des Radius, Plummer: Φ = -μ / √(r² + ε²))
+    pub fn potential(&self, distance: f64, mu: f64) -> f64 {
+        match *self {
+            GravitySoftening::Cutoff(radius) => {
+                if distance <= radius {
+                    0.0
+                } else {
+                    -mu / distance
+                }
+            }
+            GravitySoftening::Plummer(epsilon) => {
+                let softened = (distance * distance + epsilon * epsilon).sqrt();
+                if softened == 0.0 {
+                    0.0
+                } else {
+                    -mu / softened
+                }
+            }
+        }
+    }
+}
+
+impl Default for GravitySoftening {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Satz physikalischer Konstanten
+///
+/// Standardwerte entsprechen den Modul-Konstanten; für analytische Tests
+/// können vereinfachte Werte (z.B. G = 1) gesetzt werden.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PhysicsConstants {
+    /// Gravitationskonstante [m³/(kg·s²)]
+    pub g: f64,
+    /// Erdmasse [kg]
+    pub m_earth: f64,
+    /// Mondmasse [kg]
+    pub m_moon: f64,
+    /// Erdradius [m]
+    pub r_earth: f64,
+    /// Mondradius [m]
+    pub r_moon: f64,
+    /// Normfallbeschleunigung [m/s²]
+    pub g0: f64,
+    /// Singularitätsbehandlung nahe r = 0
+    pub softening: GravitySoftening,
+}
+
+impl PhysicsConstants {
+    /// Reale Werte (identisch mit den Modul-Konstanten)
+    pub const STANDARD: Self = Self {
+        g: G,
+        m_earth: M_EARTH,
+        m_moon: M_MOON,
+        r_earth: R_EARTH,
+        r_moon: R_MOON,
+        g0: G0,
+        softening: GravitySoftening::DEFAULT,
+    };
+
+    /// Erde als gravitierender Körper
+    pub fn earth(&self, position: Vector3<f64>) -> GravBody {
+        GravBody {
+            name: "Earth",
+            position,
+            mu: self.g * self.m_earth,
+            radius: self.r_earth,
+            terrain_height: flat_terrain,
+        }
+    }
+
+    /// Mond als gravitierender Körper
+    pub fn moon(&self, position: Vector3<f64>) -> GravBody {
+        GravBody {
+            name: "Moon",
+            position,
+            mu: self.g * self.m_moon,
+            radius: self.r_moon,
+            terrain_height: flat_terrain,
+        }
+    }
+
+    /// F = G * m1 * m2 / r² (Richtung: von m1 zu m2)
+    pub fn gravitational_force(
+        &self,
+        pos1: &Vector3<f64>,
+        mass1: f64,
+        pos2: &Vector3<f64>,
+        mass2: f64,
+    ) -> Vector3<f64> {
+        self.softening
+            .acceleration(&(pos2 - pos1), self.g * mass1 * mass2)
+    }
+
+    /// Gravitationsbeschleunigung von Erde und Mond
+    pub fn gravity_acceleration(
+        &self,
+        spacecraft_pos: &Vector3<f64>,
+        earth_pos: &Vector3<f64>,
+        moon_pos: &Vector3<f64>,
+    ) -> Vector3<f64> {
+        self.gravity_acceleration_bodies(
+            spacecraft_pos,
+            &[self.earth(*earth_pos), self.moon(*moon_pos)],
+        )
+    }
+
+    /// Gravitationsbeschleunigung einer Liste von Körpern (mit `softening`)
+    pub fn gravity_acceleration_bodies(
+        &self,
+        spacecraft_pos: &Vector3<f64>,
+        bodies: &[GravBody],
+    ) -> Vector3<f64> {
+        bodies
+            .iter()
+            .map(|body| {
+                self.softening
+                    .acceleration(&(body.position - spacecraft_pos), body.mu)
+            })
+            .sum()
+    }
+
+    /// Spezifisches Gravitationspotential einer Liste von Körpern (mit `softening`)
+    pub fn gravitational_potential(
+        &self,
+        spacecraft_pos: &Vector3<f64>,
+        bodies: &[GravBody],
+    ) -> f64 {
+        bodies
+            .iter()
+            .map(|body| {
+                let distance = (body.position - spacecraft_pos).norm();
+                self.softening.potential(distance, body.mu)
+            })
+            .sum()
+    }
+
+    /// ṁ = T / (Isp * g0)
+    pub fn propellant_mass_flow(&self, thrust_magnitude: f64, isp: f64) -> f64 {
+        if isp > 0.0 {
+            thrust_magnitude / (isp * self.g0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Tatsächlich wirkender Schub eines Schritts der Länge `dt`
+    ///
+    /// Reicht der Treibstoff `propellant` [kg] nicht für den vollen Schub über
+    /// den Schritt, wird der Schub so gedrosselt, dass er genau aufgebraucht wird.
+    pub fn available_thrust(
+        &self,
+        thrust: &Vector3<f64>,
+        isp: f64,
+        propellant: f64,
+        dt: f64,
+    ) -> Vector3<f64> {
+        let propellant = propellant.max(0.0);
+        let requested_flow = self.propellant_mass_flow(thrust.norm(), isp);
+        if requested_flow * dt > propellant {
+            thrust * (propellant / (requested_flow * dt))
+        } else {
+            *thrust
+        }
+    }
+}
+
+impl Default for PhysicsConstants {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
 /// Zustand des Raumschiffs
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpacecraftState {
     /// Position [m] im inertialen Referenzsystem
     pub position: Vector3<f64>,
@@ -44,6 +308,264 @@ impl SpacecraftState {
             time: 0.0,
         }
     }
+
+    /// Höhe über der Oberfläche eines Körpers [m]
+    pub fn altitude_above(&self, body_pos: &Vector3<f64>, body_radius: f64) -> f64 {
+        (self.position - body_pos).norm() - body_radius
+    }
+
+    /// Betrag der Geschwindigkeit [m/s]
+    pub fn speed(&self) -> f64 {
+        self.velocity.norm()
+    }
+
+    /// Alle Komponenten endlich (kein NaN/∞ in Position, Geschwindigkeit, Masse, Zeit)
+    pub fn is_finite(&self) -> bool {
+        self.position
+            .iter()
+            .chain(self.velocity.iter())
+            .all(|v| v.is_finite())
+            && self.mass.is_finite()
+            && self.time.is_finite()
+    }
+
+    /// Verbleibendes Δv nach Raketengleichung: Δv = Isp * g0 * ln(m / m_dry) [m/s]
+    pub fn remaining_delta_v(&self, dry_mass: f64, isp: f64) -> f64 {
+        if self.mass <= dry_mass {
+            return 0.0;
+        }
+        isp * G0 * (self.mass / dry_mass).ln()
+    }
+
+    /// Serialisiert den Zustand als JSON (verlustfrei, z.B. für Checkpoints)
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("SpacecraftState ist immer als JSON darstellbar")
+    }
+
+    /// Liest einen mit `to_json` geschriebenen Zustand
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Gravitierender Himmelskörper (Punktmasse mit Oberfläche)
+#[derive(Debug, Clone, Copy)]
+pub struct GravBody {
+    pub name: &'static str,
+    /// Position [m]
+    pub position: Vector3<f64>,
+    /// Gravitationsparameter μ = G * M [m³/s²]
+    pub mu: f64,
+    /// Radius [m]
+    pub radius: f64,
+    /// Geländehöhe über `radius` [m] für (Breite, Länge) [°], körperfest
+    /// (nicht rotierend)
+    pub terrain_height: fn(f64, f64) -> f64,
+}
+
+/// Ebenes Gelände: Oberfläche auf dem Referenzradius
+pub fn flat_terrain(_latitude: f64, _longitude: f64) -> f64 {
+    0.0
+}
+
+/// Vergleicht nur die physikalischen Größen: Funktionszeiger haben keine
+/// eindeutige Adresse, die Geländehöhe ist daher nicht vergleichbar
+impl PartialEq for GravBody {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.position == other.position
+            && self.mu == other.mu
+            && self.radius == other.radius
+    }
+}
+
+impl GravBody {
+    pub fn earth(position: Vector3<f64>) -> Self {
+        PhysicsConstants::STANDARD.earth(position)
+    }
+
+    pub fn moon(position: Vector3<f64>) -> Self {
+        PhysicsConstants::STANDARD.moon(position)
+    }
+
+    /// Höhe über dem lokalen Gelände [m] (≤ 0: Oberfläche durchdrungen)
+    pub fn surface_altitude(&self, position: &Vector3<f64>) -> f64 {
+        let relative = position - self.position;
+        let (latitude, longitude) = ground_track(&relative, 0.0, 0.0);
+        relative.norm() - self.radius - (self.terrain_height)(latitude, longitude)
+    }
+}
+
+/// Zielkörper der Mission (Standard: Mond)
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TargetBody {
+    pub name: &'static str,
+    /// Radius [m]
+    pub radius: f64,
+    /// Gravitationsparameter μ = G * M [m³/s²]
+    pub mu: f64,
+    /// Abstand vom Erdmittelpunkt [m]
+    pub distance_from_earth: f64,
+    /// Geländehöhe über `radius` [m] für (Breite, Länge) [°]; nicht Teil des
+    /// Missionsprofils (eingelesen wird ebenes Gelände)
+    #[serde(skip)]
+    pub terrain_height: fn(f64, f64) -> f64,
+}
+
+/// Zielkörper wie im Missionsprofil angegeben (Name als eigener String)
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TargetBodyProfile {
+    name: String,
+    radius: f64,
+    mu: f64,
+    distance_from_earth: f64,
+}
+
+impl<'de> Deserialize<'de> for TargetBody {
+    /// Bekannte Körper nutzen die Konstante, andere Namen werden einmalig
+    /// angelegt und leben bis Programmende (ein Profil je Lauf).
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let profile = TargetBodyProfile::deserialize(deserializer)?;
+        let name = match profile.name.as_str() {
+            "Earth" => "Earth",
+            "Moon" => "Moon",
+            _ => Box::leak(profile.name.into_boxed_str()),
+        };
+        Ok(Self {
+            name,
+            radius: profile.radius,
+            mu: profile.mu,
+            distance_from_earth: profile.distance_from_earth,
+            terrain_height: flat_terrain,
+        })
+    }
+}
+
+/// Vergleicht nur die physikalischen Größen (siehe `GravBody`)
+impl PartialEq for TargetBody {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.radius == other.radius
+            && self.mu == other.mu
+            && self.distance_from_earth == other.distance_from_earth
+    }
+}
+
+impl TargetBody {
+    pub fn moon() -> Self {
+        Self {
+            name: "Moon",
+            radius: R_MOON,
+            mu: G * M_MOON,
+            distance_from_earth: EARTH_MOON_DISTANCE,
+            terrain_height: flat_terrain,
+        }
+    }
+
+    /// Gravitierender Körper an gegebener Position
+    pub fn grav_body(&self, position: Vector3<f64>) -> GravBody {
+        GravBody {
+            name: self.name,
+            position,
+            mu: self.mu,
+            radius: self.radius,
+            terrain_height: self.terrain_height,
+        }
+    }
+}
+
+impl Default for TargetBody {
+    fn default() -> Self {
+        Self::moon()
+    }
+}
+
+/// Oberflächenkontakt mit einem Himmelskörper
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollisionEvent {
+    /// Name des getroffenen Körpers
+    pub body: &'static str,
+    /// Aufprallgeschwindigkeit [m/s]
+    pub impact_speed: f64,
+    /// Höhe über der Oberfläche zum Erkennungszeitpunkt [m] (≤ 0)
+    pub altitude: f64,
+}
+
+/// Prüft ob das Raumschiff die Oberfläche eines Körpers (einschließlich
+/// Geländehöhe) durchdrungen hat
+pub fn check_collision(state: &SpacecraftState, bodies: &[GravBody]) -> Option<CollisionEvent> {
+    bodies.iter().find_map(|body| {
+        let altitude = body.surface_altitude(&state.position);
+        (altitude <= 0.0).then(|| CollisionEvent {
+            body: body.name,
+            impact_speed: state.velocity.norm(),
+            altitude,
+        })
+    })
+}
+
+/// Erster Oberflächenkontakt zwischen zwei aufeinanderfolgenden Zuständen
+///
+/// Die Bahn im Intervall wird kubisch hermitesch aus Position und
+/// Geschwindigkeit beider Enden interpoliert, damit auch ein Durchflug
+/// innerhalb des Schritts (Endzustand wieder über der Oberfläche) erkannt
+/// wird. Den Kontaktzeitpunkt bestimmt eine Bisektion; der zurückgegebene
+/// Zustand liegt höchstens 1 mm unter der Oberfläche. Körper gelten über das
+/// Intervall als ruhend. Lag bereits `previous` auf oder unter einer
+/// Oberfläche, gibt es keinen neuen Kontakt.
+pub fn find_surface_contact(
+    previous: &SpacecraftState,
+    current: &SpacecraftState,
+    bodies: &[GravBody],
+) -> Option<SpacecraftState> {
+    const SAMPLES: u32 = 16;
+    let dt = current.time - previous.time;
+    let (p0, p1) = (previous.position, current.position);
+    let (m0, m1) = (previous.velocity * dt, current.velocity * dt);
+    let state_at = |s: f64| {
+        let (s2, s3) = (s * s, s * s * s);
+        let position = p0 * (2.0 * s3 - 3.0 * s2 + 1.0)
+            + m0 * (s3 - 2.0 * s2 + s)
+            + p1 * (-2.0 * s3 + 3.0 * s2)
+            + m1 * (s3 - s2);
+        let velocity = (p0 * (6.0 * s2 - 6.0 * s)
+            + m0 * (3.0 * s2 - 4.0 * s + 1.0)
+            + p1 * (-6.0 * s2 + 6.0 * s)
+            + m1 * (3.0 * s2 - 2.0 * s))
+            / dt;
+        SpacecraftState {
+            position,
+            velocity,
+            mass: previous.mass + s * (current.mass - previous.mass),
+            time: previous.time + s * dt,
+        }
+    };
+    let altitude = |s: f64| {
+        let position = state_at(s).position;
+        bodies
+            .iter()
+            .map(|b| b.surface_altitude(&position))
+            .fold(f64::INFINITY, f64::min)
+    };
+    if dt <= 0.0 || altitude(0.0) <= 0.0 {
+        return None;
+    }
+
+    // Erstes Teilintervall mit Vorzeichenwechsel, dann Bisektion
+    let mut high = (1..=SAMPLES)
+        .map(|k| k as f64 / SAMPLES as f64)
+        .find(|&s| altitude(s) <= 0.0)?;
+    let mut low = high - 1.0 / SAMPLES as f64;
+    while altitude(high) < -1e-3 && high - low > f64::EPSILON {
+        let mid = 0.5 * (low + high);
+        if altitude(mid) <= 0.0 {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    Some(state_at(high))
 }
 
 /// Berechnet Gravitationskraft zwischen zwei Körpern
@@ -54,13 +576,7 @@ pub fn gravitational_force(
     pos2: &Vector3<f64>,
     mass2: f64,
 ) -> Vector3<f64> {
-    let r = pos2 - pos1;
-    let distance = r.norm();
-    if distance < 1.0 {
-        return Vector3::zeros(); // Singularität vermeiden
-    }
-    let force_magnitude = G * mass1 * mass2 / (distance * distance);
-    r.normalize() * force_magnitude
+    PhysicsConstants::STANDARD.gravitational_force(pos1, mass1, pos2, mass2)
 }
 
 /// Berechnet Gravitationsbeschleunigung auf das Raumschiff
@@ -70,25 +586,648 @@ pub fn gravity_acceleration(
     earth_pos: &Vector3<f64>,
     moon_pos: &Vector3<f64>,
 ) -> Vector3<f64> {
-    // Richtung zu Erde
-    let r_earth = earth_pos - spacecraft_pos;
-    let d_earth = r_earth.norm();
-    let a_earth = if d_earth > 1.0 {
-        r_earth.normalize() * (G * M_EARTH / (d_earth * d_earth))
-    } else {
-        Vector3::zeros()
+    PhysicsConstants::STANDARD.gravity_acceleration(spacecraft_pos, earth_pos, moon_pos)
+}
+
+/// Summierte Gravitationsbeschleunigung beliebiger Körper
+/// a = Σ μ_i * r̂_i / r_i²
+pub fn gravity_acceleration_bodies(
+    spacecraft_pos: &Vector3<f64>,
+    bodies: &[GravBody],
+) -> Vector3<f64> {
+    PhysicsConstants::STANDARD.gravity_acceleration_bodies(spacecraft_pos, bodies)
+}
+
+/// Spezifische mechanische Gesamtenergie [J/kg]
+/// ε = v²/2 - Σ μ_i / r_i
+///
+/// Bleibt auf antriebslosen Bahnbögen (bei ruhenden Körpern) konstant und
+/// eignet sich daher als Gütemaß für den Integrator.
+pub fn total_specific_energy(state: &SpacecraftState, bodies: &[GravBody]) -> f64 {
+    0.5 * state.velocity.norm_squared() + gravitational_potential(&state.position, bodies)
+}
+
+/// Spezifische Zweikörper-Bahnenergie relativ zu `body` [J/kg]
+/// ε = v²/2 - μ / r  (ε > 0: hyperbolisch, ungebunden)
+pub fn specific_energy(state: &SpacecraftState, body: &GravBody) -> f64 {
+    let r = (state.position - body.position).norm();
+    0.5 * state.velocity.norm_squared() - body.mu / r
+}
+
+/// Große Halbachse der Zweikörperbahn um `body` aus der Vis-Viva-Gleichung [m]
+/// a = -μ / (2 ε)  (negativ für hyperbolische Bahnen)
+pub fn semi_major_axis(state: &SpacecraftState, body: &GravBody) -> f64 {
+    -body.mu / (2.0 * specific_energy(state, body))
+}
+
+/// Umlaufzeit einer Ellipsenbahn [s]: T = 2π √(a³ / μ)
+///
+/// Für ungebundene Bahnen (a ≤ 0) unendlich.
+pub fn orbital_period(semi_major_axis: f64, mu: f64) -> f64 {
+    if semi_major_axis <= 0.0 {
+        return f64::INFINITY;
+    }
+    2.0 * std::f64::consts::PI / mean_motion(semi_major_axis, mu)
+}
+
+/// Mittlere Bewegung [rad/s]: n = √(μ / a³) (0 für ungebundene Bahnen)
+pub fn mean_motion(semi_major_axis: f64, mu: f64) -> f64 {
+    if semi_major_axis <= 0.0 {
+        return 0.0;
+    }
+    (mu / semi_major_axis.powi(3)).sqrt()
+}
+
+/// Fluchtbahn: ungebunden bezüglich `primary`, von ihm fort fliegend und
+/// weiter als `escape_radius` [m] entfernt (z.B. jenseits der Einflusssphäre
+/// des Ziels, das dann nicht mehr erreicht werden kann)
+pub fn is_escape_trajectory(
+    state: &SpacecraftState,
+    primary: &GravBody,
+    escape_radius: f64,
+) -> bool {
+    let r = state.position - primary.position;
+    specific_energy(state, primary) > 0.0
+        && r.dot(&state.velocity) > 0.0
+        && r.norm() > escape_radius
+}
+
+/// Bahnneigungswinkel γ [rad]: Winkel zwischen Geschwindigkeit und lokaler
+/// Horizontalen bezüglich `body_pos` (positiv steigend, ±π/2 rein radial)
+///
+/// Liefert 0 für verschwindende Geschwindigkeit oder Position im Körperzentrum.
+pub fn flight_path_angle(
+    position: &Vector3<f64>,
+    velocity: &Vector3<f64>,
+    body_pos: &Vector3<f64>,
+) -> f64 {
+    let r = position - body_pos;
+    let speed = velocity.norm();
+    if speed < 1e-9 || r.norm() < 1e-9 {
+        return 0.0;
+    }
+    let sin_gamma = r.normalize().dot(velocity) / speed;
+    sin_gamma.clamp(-1.0, 1.0).asin()
+}
+
+/// Spezifisches Gravitationspotential [J/kg]
+/// Φ = -Σ μ_i / r_i
+pub fn gravitational_potential(spacecraft_pos: &Vector3<f64>, bodies: &[GravBody]) -> f64 {
+    PhysicsConstants::STANDARD.gravitational_potential(spacecraft_pos, bodies)
+}
+
+/// Zeitableitung des Bahnzustands [x, y, z, vx, vy, vz] unter Gravitation
+/// ẋ = [v, a(r)]
+pub fn state_derivative(state: &Vector6<f64>, bodies: &[GravBody]) -> Vector6<f64> {
+    let position = Vector3::new(state[0], state[1], state[2]);
+    let a = gravity_acceleration_bodies(&position, bodies);
+    Vector6::new(state[3], state[4], state[5], a.x, a.y, a.z)
+}
+
+/// Gravitationsgradient ∂a/∂r (Gezeitentensor)
+/// ∂a/∂r = Σ μ_i / r_i³ * (3 r̂_i r̂_iᵀ - I)
+///
+/// Spurfrei außerhalb der Massen; direkt nutzbar für die F-Matrix eines EKF.
+pub fn gravity_gradient(spacecraft_pos: &Vector3<f64>, bodies: &[GravBody]) -> Matrix3<f64> {
+    bodies
+        .iter()
+        .map(|body| {
+            let r = spacecraft_pos - body.position;
+            let d = r.norm();
+            if d < 1.0 {
+                Matrix3::zeros() // Singularität vermeiden
+            } else {
+                let r_hat = r / d;
+                (3.0 * r_hat * r_hat.transpose() - Matrix3::identity()) * (body.mu / (d * d * d))
+            }
+        })
+        .sum()
+}
+
+/// Maximaler Vorhersagehorizont für `predict_impact` [s]
+pub const IMPACT_HORIZON: f64 = 2.0 * 86_400.0;
+
+/// Geozentrische Sonnenposition (Kreisbahn in der Erde-Mond-Ebene, t = 0 auf +x) [m]
+pub fn sun_position(time: f64) -> Vector3<f64> {
+    let angle = 2.0 * std::f64::consts::PI * time / SIDEREAL_YEAR;
+    Vector3::new(angle.cos(), angle.sin(), 0.0) * AU
+}
+
+/// Tabellierte Mondposition über der Zeit (z.B. aus einer echten Ephemeride)
+///
+/// Zwischen den Stützstellen linear interpoliert, außerhalb des Bereichs
+/// auf die erste bzw. letzte Stützstelle begrenzt. Im Missionsprofil als
+/// Liste von `[t, [x, y, z]]`-Paaren.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    try_from = "Vec<(f64, Vector3<f64>)>",
+    into = "Vec<(f64, Vector3<f64>)>"
+)]
+pub struct MoonEphemeris {
+    /// Stützstellen (Zeit [s], geozentrische Position [m]), nach Zeit sortiert
+    samples: Vec<(f64, Vector3<f64>)>,
+}
+
+impl MoonEphemeris {
+    /// Erstellt die Tabelle; `None` ohne Stützstellen
+    pub fn new(mut samples: Vec<(f64, Vector3<f64>)>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Some(Self { samples })
+    }
+
+    /// Stützstellen in zeitlicher Reihenfolge
+    pub fn samples(&self) -> &[(f64, Vector3<f64>)] {
+        &self.samples
+    }
+
+    /// Mondposition zur Zeit `time` [s]
+    pub fn position_at(&self, time: f64) -> Vector3<f64> {
+        let after = self.samples.partition_point(|(t, _)| *t <= time);
+        if after == 0 {
+            return self.samples[0].1;
+        }
+        if after == self.samples.len() {
+            return self.samples[after - 1].1;
+        }
+        let (t0, p0) = self.samples[after - 1];
+        let (t1, p1) = self.samples[after];
+        p0.lerp(&p1, (time - t0) / (t1 - t0))
+    }
+
+    /// Mondgeschwindigkeit zur Zeit `time` [m/s] (Steigung des interpolierten
+    /// Abschnitts, außerhalb des Bereichs 0)
+    pub fn velocity_at(&self, time: f64) -> Vector3<f64> {
+        let after = self.samples.partition_point(|(t, _)| *t <= time);
+        if after == 0 || after == self.samples.len() {
+            return Vector3::zeros();
+        }
+        let (t0, p0) = self.samples[after - 1];
+        let (t1, p1) = self.samples[after];
+        (p1 - p0) / (t1 - t0)
+    }
+}
+
+impl TryFrom<Vec<(f64, Vector3<f64>)>> for MoonEphemeris {
+    type Error = &'static str;
+
+    fn try_from(samples: Vec<(f64, Vector3<f64>)>) -> Result<Self, Self::Error> {
+        Self::new(samples).ok_or("Ephemeride ohne Stützstellen")
+    }
+}
+
+impl From<MoonEphemeris> for Vec<(f64, Vector3<f64>)> {
+    fn from(ephemeris: MoonEphemeris) -> Self {
+        ephemeris.samples
+    }
+}
+
+/// Einfaches Umgebungsmodell für die Sensor-Telemetrie (geozentrische Positionen)
+///
+/// Rückgabe (Temperatur [°C], Druck [kPa], Strahlung [mSv/h]):
+/// - Temperatur: besonnt oder im zylindrischen Erdschatten
+/// - Druck: exponentielle Erdatmosphäre (SCALE_HEIGHT wie im Luftwiderstand)
+/// - Strahlung: kosmischer Hintergrund (mit der Sonnenrotation moduliert)
+///   plus innerer und äußerer Van-Allen-Gürtel als Gauß-Profile über der Höhe
+pub fn environment_at(
+    position: &Vector3<f64>,
+    time: f64,
+    sun_pos: &Vector3<f64>,
+) -> (f32, f32, f32) {
+    let altitude = position.norm() - R_EARTH;
+
+    let in_shadow = in_umbra(position, sun_pos, &GravBody::earth(Vector3::zeros()));
+    let temperature = if in_shadow { -150.0 } else { 120.0 };
+
+    let pressure = SEA_LEVEL_PRESSURE * (-altitude.max(0.0) / SCALE_HEIGHT).exp();
+
+    let belt = |center: f64, width: f64, peak: f64| {
+        peak * (-0.5 * ((altitude - center) / width).powi(2)).exp()
     };
+    let modulation = 1.0 + 0.1 * (2.0 * std::f64::consts::PI * time / SOLAR_ROTATION).sin();
+    let radiation = 0.02 * modulation
+        + belt(5_000_000.0, 1_500_000.0, 5.0)
+        + belt(20_000_000.0, 5_000_000.0, 1.0);
 
-    // Richtung zu Mond
-    let r_moon = moon_pos - spacecraft_pos;
-    let d_moon = r_moon.norm();
-    let a_moon = if d_moon > 1.0 {
-        r_moon.normalize() * (G * M_MOON / (d_moon * d_moon))
-    } else {
-        Vector3::zeros()
+    (temperature, pressure as f32, radiation as f32)
+}
+
+/// Liegt `position` im zylindrischen Kernschatten (Umbra) von `body`?
+pub fn in_umbra(position: &Vector3<f64>, sun_pos: &Vector3<f64>, body: &GravBody) -> bool {
+    let relative = position - body.position;
+    let sun_dir = (sun_pos - body.position).normalize();
+    let along = relative.dot(&sun_dir);
+    along < 0.0 && (relative - sun_dir * along).norm() < body.radius
+}
+
+/// Beschleunigung durch solaren Strahlungsdruck [m/s²]
+///
+/// a = S(d) / c · (1 + ρ) · A / m, von der Sonne weg gerichtet, mit der
+/// Solarkonstante S(d) = S₀ · (AU / d)² im Abstand d. Null im zylindrischen
+/// Kernschatten eines der Körper in `bodies`.
+pub fn srp_acceleration(
+    position: &Vector3<f64>,
+    sun_pos: &Vector3<f64>,
+    mass: f64,
+    area: f64,
+    reflectivity: f64,
+    bodies: &[GravBody],
+) -> Vector3<f64> {
+    let from_sun = position - sun_pos;
+    let distance = from_sun.norm();
+    if mass <= 0.0 || distance == 0.0 || bodies.iter().any(|b| in_umbra(position, sun_pos, b)) {
+        return Vector3::zeros();
+    }
+    let pressure = SOLAR_CONSTANT / SPEED_OF_LIGHT * (AU / distance).powi(2);
+    from_sun / distance * (pressure * (1.0 + reflectivity) * area / mass)
+}
+
+/// Strahlungsdruck-Modell des Fahrzeugs (Kanonenkugel-Modell)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SolarRadiationPressure {
+    /// Angestrahlte Fläche [m²]
+    pub area: f64,
+    /// Reflexionsgrad ρ (0 = absorbierend, 1 = spiegelnd)
+    pub reflectivity: f64,
+}
+
+impl SolarRadiationPressure {
+    /// Strahlungsdruck-Beschleunigung im geozentrischen Zustand `state` [m/s²]
+    /// (Sonne aus `sun_position`, Schatten der Körper in `bodies`)
+    pub fn acceleration(&self, state: &SpacecraftState, bodies: &[GravBody]) -> Vector3<f64> {
+        srp_acceleration(
+            &state.position,
+            &sun_position(state.time),
+            state.mass,
+            self.area,
+            self.reflectivity,
+            bodies,
+        )
+    }
+}
+
+/// Störbeschleunigung durch die Abplattung (J2) von `body` [m/s²]
+///
+/// Polachse entlang +z: a = 3/2 · J2 · μ · R² / r⁵ · (x (5z²/r² - 1),
+/// y (5z²/r² - 1), z (5z²/r² - 3)), `position` absolut.
+pub fn j2_acceleration(position: &Vector3<f64>, body: &GravBody, j2: f64) -> Vector3<f64> {
+    let r = position - body.position;
+    let distance = r.norm();
+    if distance == 0.0 {
+        return Vector3::zeros();
+    }
+    let factor = 1.5 * j2 * body.mu * body.radius.powi(2) / distance.powi(5);
+    let z2 = 5.0 * r.z * r.z / (distance * distance);
+    Vector3::new(r.x * (z2 - 1.0), r.y * (z2 - 1.0), r.z * (z2 - 3.0)) * factor
+}
+
+/// Gezeitenbeschleunigung der Sonne im geozentrischen System [m/s²]
+///
+/// Direkter Term abzüglich der Beschleunigung der Erde selbst:
+/// a = μ☉ · ((r☉ - r) / |r☉ - r|³ - r☉ / |r☉|³)
+pub fn sun_third_body_acceleration(
+    position: &Vector3<f64>,
+    sun_pos: &Vector3<f64>,
+) -> Vector3<f64> {
+    let to_sun = sun_pos - position;
+    MU_SUN * (to_sun / to_sun.norm().powi(3) - sun_pos / sun_pos.norm().powi(3))
+}
+
+/// Luftwiderstand in der exponentiellen Atmosphäre von `earth` [m/s²]
+///
+/// a = -½ · ρ(h) · c_W · A / m · |v_rel| · v_rel, Anströmung relativ zur
+/// mitrotierenden Atmosphäre (Drehachse +z).
+pub fn drag_acceleration(
+    state: &SpacecraftState,
+    earth: &GravBody,
+    area: f64,
+    drag_coefficient: f64,
+) -> Vector3<f64> {
+    if state.mass <= 0.0 {
+        return Vector3::zeros();
+    }
+    let r = state.position - earth.position;
+    let altitude = r.norm() - earth.radius;
+    let density = SEA_LEVEL_DENSITY * (-altitude.max(0.0) / SCALE_HEIGHT).exp();
+    let omega = Vector3::new(0.0, 0.0, EARTH_ROTATION_RATE);
+    let relative = state.velocity - omega.cross(&r);
+    -0.5 * density * drag_coefficient * area / state.mass * relative.norm() * relative
+}
+
+/// Luftwiderstandsmodell des Fahrzeugs
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AtmosphericDrag {
+    /// Angeströmte Querschnittsfläche [m²]
+    pub area: f64,
+    /// Widerstandsbeiwert c_W
+    pub drag_coefficient: f64,
+}
+
+/// Kraftmodell der Bahnpropagation über die Punktmassen-Gravitation hinaus
+///
+/// Die Punktmassen-Gravitation der Körper ist stets aktiv; jede Störkraft
+/// ist einzeln zuschaltbar (Standard: alle aus). J2 und Luftwiderstand
+/// beziehen sich auf den übergebenen Zentralkörper.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ForceModel {
+    /// Abplattung der Erde (J2)
+    pub j2: bool,
+    /// Sonne als dritter Körper
+    pub sun: bool,
+    /// Solarer Strahlungsdruck (None = vernachlässigt)
+    pub srp: Option<SolarRadiationPressure>,
+    /// Luftwiderstand (None = vernachlässigt)
+    pub drag: Option<AtmosphericDrag>,
+}
+
+impl ForceModel {
+    /// Summe der aktivierten Störbeschleunigungen im Zustand `state` [m/s²]
+    ///
+    /// `central` ist die Erde (J2, Atmosphäre), `bodies` alle Körper für die
+    /// Schattenprüfung des Strahlungsdrucks.
+    pub fn acceleration(
+        &self,
+        state: &SpacecraftState,
+        central: &GravBody,
+        bodies: &[GravBody],
+    ) -> Vector3<f64> {
+        let mut acceleration = Vector3::zeros();
+        if self.j2 {
+            acceleration += j2_acceleration(&state.position, central, J2_EARTH);
+        }
+        if self.sun {
+            acceleration += sun_third_body_acceleration(&state.position, &sun_position(state.time));
+        }
+        if let Some(srp) = &self.srp {
+            acceleration += srp.acceleration(state, bodies);
+        }
+        if let Some(drag) = &self.drag {
+            acceleration += drag_acceleration(state, central, drag.area, drag.drag_coefficient);
+        }
+        acceleration
+    }
+}
+
+/// Ballistische Aufschlagvorhersage auf `target`
+///
+/// Propagiert ohne Schub (RK4, Schrittweite an Höhe und Geschwindigkeit
+/// angepasst) bis zum Oberflächenkontakt. Rückgabe: (Zeit bis Aufschlag [s],
+/// Aufschlagposition), `None` ohne Aufschlag innerhalb von IMPACT_HORIZON.
+pub fn predict_impact(
+    state: &SpacecraftState,
+    bodies: &[GravBody],
+    target: &GravBody,
+) -> Option<(f64, Vector3<f64>)> {
+    let altitude = |s: &SpacecraftState| s.altitude_above(&target.position, target.radius);
+    if altitude(state) <= 0.0 {
+        return Some((0.0, state.position));
+    }
+
+    let mut current = state.clone();
+    while current.time - state.time < IMPACT_HORIZON {
+        let dt = (0.01 * altitude(&current) / current.speed().max(1.0)).clamp(0.01, 60.0);
+        let previous = current.clone();
+        integrate_rk4_with(
+            &mut current,
+            |s| (gravity_acceleration_bodies(&s.position, bodies), 0.0),
+            dt,
+        );
+
+        let alt = altitude(&current);
+        if alt <= 0.0 {
+            // Lineare Interpolation auf die Oberfläche
+            let alt_previous = altitude(&previous);
+            let fraction = alt_previous / (alt_previous - alt);
+            let time = previous.time + fraction * dt - state.time;
+            let position = previous.position.lerp(&current.position, fraction);
+            return Some((time, position));
+        }
+    }
+    None
+}
+
+/// Ballistisch vorhergesagte größte Annäherung an den Mond (Perilunäum)
+///
+/// Propagiert ohne Schub im geozentrischen System (Erde im Ursprung, Mond bei
+/// `moon_position(t)`, z.B. `MoonEphemeris::position_at`) über `horizon` [s].
+/// Rückgabe: (Minimalabstand zum Mondzentrum [m], Zeit bis dahin [s]); bei
+/// einem Aufschlag auf Mond oder Erde endet die Suche (wie `predict_impact`).
+pub fn closest_approach<F>(state: &SpacecraftState, moon_position: F, horizon: f64) -> (f64, f64)
+where
+    F: Fn(f64) -> Vector3<f64>,
+{
+    let bodies = |time: f64| {
+        [
+            GravBody::earth(Vector3::zeros()),
+            GravBody::moon(moon_position(time)),
+        ]
+    };
+    let distance = |s: &SpacecraftState| (s.position - moon_position(s.time)).norm();
+
+    let mut current = state.clone();
+    let mut closest = (distance(state), 0.0);
+    while current.time - state.time < horizon && closest.0 > R_MOON {
+        let closest_surface = bodies(current.time)
+            .iter()
+            .map(|b| current.altitude_above(&b.position, b.radius))
+            .fold(f64::INFINITY, f64::min);
+        let dt = (0.01 * closest_surface / current.speed().max(1.0)).clamp(0.01, 60.0);
+        integrate_rk4_with(
+            &mut current,
+            |s| {
+                (
+                    gravity_acceleration_bodies(&s.position, &bodies(s.time)),
+                    0.0,
+                )
+            },
+            dt,
+        );
+
+        let d = distance(&current);
+        if d < closest.0 {
+            closest = (d.max(R_MOON), current.time - state.time);
+        }
+        if check_collision(&current, &bodies(current.time)).is_some() {
+            break;
+        }
+    }
+    closest
+}
+
+/// Höchste Perigäumshöhe, die noch als Rückkehr (Wiedereintritt) zählt [m]
+pub const FREE_RETURN_MAX_PERIGEE: f64 = 150_000.0;
+
+/// Prüft, ob ein antriebsloser Bogen innerhalb von `horizon` [s] zur Erde zurückkehrt
+///
+/// Zählt erst nach Durchlaufen des Apogäums: Perigäumshöhe unter
+/// FREE_RETURN_MAX_PERIGEE, ohne vorher einen anderen Körper zu treffen.
+/// Die Erde wird über ihren Namen in `bodies` gefunden.
+pub fn is_free_return(state: &SpacecraftState, bodies: &[GravBody], horizon: f64) -> bool {
+    let Some(earth) = bodies.iter().find(|b| b.name == "Earth") else {
+        return false;
     };
+    let radial_velocity = |s: &SpacecraftState| (s.position - earth.position).dot(&s.velocity);
 
-    a_earth + a_moon
+    let mut current = state.clone();
+    let mut past_apogee = false;
+    while current.time - state.time < horizon {
+        let closest_surface = bodies
+            .iter()
+            .map(|b| current.altitude_above(&b.position, b.radius))
+            .fold(f64::INFINITY, f64::min);
+        let dt = (0.01 * closest_surface / current.speed().max(1.0)).clamp(0.01, 60.0);
+        integrate_rk4_with(
+            &mut current,
+            |s| (gravity_acceleration_bodies(&s.position, bodies), 0.0),
+            dt,
+        );
+
+        if let Some(collision) = check_collision(&current, bodies) {
+            return collision.body == earth.name && past_apogee;
+        }
+        past_apogee |= radial_velocity(&current) < 0.0;
+        if past_apogee
+            && current.altitude_above(&earth.position, earth.radius) < FREE_RETURN_MAX_PERIGEE
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// Analytische Abschätzung eines Erde-Mond-Transfers (Patched Conics)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransferEstimate {
+    /// TLI-Geschwindigkeitsänderung aus der Parkbahn [m/s]
+    pub tli_delta_v: f64,
+    /// Flugzeit von TLI bis zum Eintritt in die Einflusssphäre des Mondes [s]
+    pub coast_time: f64,
+    /// Hyperbolische Exzessgeschwindigkeit relativ zum Mond [m/s]
+    pub arrival_v_infinity: f64,
+    /// Bremsmanöver in die kreisförmige Mondumlaufbahn [m/s]
+    pub loi_delta_v: f64,
+}
+
+/// Patched-Conic-Abschätzung des Transfers zum Mond
+///
+/// Hohmann-Ellipse von der kreisförmigen Parkbahn `r_park` bis zur
+/// Mondbahn (EARTH_MOON_DISTANCE), Übergang an der Einflusssphäre
+/// `soi_radius`, dort Hyperbel bis zur Mondumlaufbahn `r_moon_orbit`.
+/// Unabhängig von der numerischen Integration.
+pub fn patched_conic_transfer(
+    r_park: f64,
+    r_moon_orbit: f64,
+    mu_earth: f64,
+    mu_moon: f64,
+    soi_radius: f64,
+) -> TransferEstimate {
+    let distance = EARTH_MOON_DISTANCE;
+
+    // Transferellipse: Perigäum r_park, Apogäum auf der Mondbahn
+    let a = (r_park + distance) / 2.0;
+    let e = (distance - r_park) / (distance + r_park);
+    let v_park = (mu_earth / r_park).sqrt();
+    let v_perigee = (mu_earth * (2.0 / r_park - 1.0 / a)).sqrt();
+    let v_apogee = (mu_earth * (2.0 / distance - 1.0 / a)).sqrt();
+
+    // Flugzeit bis r = Abstand - SOI (Kepler-Gleichung)
+    let r_soi = distance - soi_radius;
+    let eccentric_anomaly = ((1.0 - r_soi / a) / e).clamp(-1.0, 1.0).acos();
+    let coast_time =
+        (a.powi(3) / mu_earth).sqrt() * (eccentric_anomaly - e * eccentric_anomaly.sin());
+
+    // Ankunft: Relativgeschwindigkeit zum Mond auf seiner Kreisbahn
+    let v_moon = (mu_earth / distance).sqrt();
+    let arrival_v_infinity = (v_moon - v_apogee).abs();
+    let v_hyperbola = (arrival_v_infinity.powi(2) + 2.0 * mu_moon / r_moon_orbit).sqrt();
+    let loi_delta_v = v_hyperbola - (mu_moon / r_moon_orbit).sqrt();
+
+    TransferEstimate {
+        tli_delta_v: v_perigee - v_park,
+        coast_time,
+        arrival_v_infinity,
+        loi_delta_v,
+    }
+}
+
+/// Lagrange-Punkte L1–L5 im mitrotierenden System [m]
+///
+/// Ursprung im Schwerpunkt, `m1` auf der negativen, `m2` auf der positiven
+/// X-Achse. Kollineare Punkte per Newton-Verfahren, L4/L5 analytisch
+/// (gleichseitige Dreiecke mit den Primärkörpern).
+pub fn lagrange_points(m1: f64, m2: f64, separation: f64) -> [Vector3<f64>; 5] {
+    let mu = m2 / (m1 + m2);
+
+    // Normierte Gleichgewichtsbedingung auf der X-Achse:
+    // f(x) = x - (1-μ)(x+μ)/|x+μ|³ - μ(x-1+μ)/|x-1+μ|³
+    let f = |x: f64| {
+        let r1 = x + mu;
+        let r2 = x - 1.0 + mu;
+        x - (1.0 - mu) * r1 / r1.abs().powi(3) - mu * r2 / r2.abs().powi(3)
+    };
+    let df = |x: f64| {
+        let r1 = (x + mu).abs();
+        let r2 = (x - 1.0 + mu).abs();
+        1.0 + 2.0 * (1.0 - mu) / r1.powi(3) + 2.0 * mu / r2.powi(3)
+    };
+    let solve = |mut x: f64| {
+        for _ in 0..50 {
+            let step = f(x) / df(x);
+            x -= step;
+            if step.abs() < 1e-15 {
+                break;
+            }
+        }
+        x
+    };
+
+    let hill = (mu / 3.0).cbrt();
+    let l1 = solve(1.0 - mu - hill);
+    let l2 = solve(1.0 - mu + hill);
+    let l3 = solve(-1.0 - 5.0 * mu / 12.0);
+    let triangle_x = 0.5 - mu;
+    let triangle_y = 3.0_f64.sqrt() / 2.0;
+
+    [
+        Vector3::new(l1, 0.0, 0.0),
+        Vector3::new(l2, 0.0, 0.0),
+        Vector3::new(l3, 0.0, 0.0),
+        Vector3::new(triangle_x, triangle_y, 0.0),
+        Vector3::new(triangle_x, -triangle_y, 0.0),
+    ]
+    .map(|p| p * separation)
+}
+
+/// Subsatellitenpunkt auf der rotierenden Erde
+///
+/// `position` ist erdzentriert im Inertialsystem, die Erde rotiert um Z.
+/// Kugelmodell, Rückgabe (Breite, Länge) in Grad, Länge in [-180°, 180°).
+pub fn ground_track(position: &Vector3<f64>, time: f64, rotation_rate: f64) -> (f64, f64) {
+    let r = position.norm();
+    if r == 0.0 {
+        return (0.0, 0.0);
+    }
+    let latitude = (position.z / r).asin().to_degrees();
+    let longitude = (position.y.atan2(position.x) - rotation_rate * time).to_degrees();
+    (latitude, (longitude + 180.0).rem_euclid(360.0) - 180.0)
+}
+
+/// Trägheitstensor des Fahrzeugs im Körpersystem [kg·m²]
+///
+/// Trockenstruktur und Treibstoff als Vollzylinder um die Schubachse Z:
+/// I_xx = I_yy = m (3r² + L²) / 12, I_zz = m r² / 2.
+/// Der Treibstoffanteil skaliert mit der verbleibenden Treibstoffmasse.
+pub fn vehicle_inertia(dry_mass: f64, mass: f64) -> Matrix3<f64> {
+    let cylinder = |m: f64, length: f64| {
+        let transverse = m * (3.0 * VEHICLE_RADIUS.powi(2) + length.powi(2)) / 12.0;
+        let axial = m * VEHICLE_RADIUS.powi(2) / 2.0;
+        Matrix3::from_diagonal(&Vector3::new(transverse, transverse, axial))
+    };
+    let propellant = (mass - dry_mass).max(0.0);
+    cylinder(dry_mass, VEHICLE_LENGTH) + cylinder(propellant, TANK_LENGTH)
 }
 
 /// Schubkraft-Beschleunigung
@@ -104,18 +1243,31 @@ pub fn thrust_acceleration(thrust: &Vector3<f64>, mass: f64) -> Vector3<f64> {
 /// Treibstoff-Massenstrom [kg/s]
 /// ṁ = -T / (Isp * g0)
 pub fn propellant_mass_flow(thrust_magnitude: f64, isp: f64) -> f64 {
-    if isp > 0.0 {
-        thrust_magnitude / (isp * G0)
-    } else {
-        0.0
-    }
+    PhysicsConstants::STANDARD.propellant_mass_flow(thrust_magnitude, isp)
+}
+
+/// Massenstrom für einen Schubvektor [kg/s]
+///
+/// Hängt ausschließlich vom Betrag des Schubs ab: ein Nullvektor (auch mit
+/// Vorzeichen-Nullen oder Richtungsresten) verbraucht keinen Treibstoff.
+pub fn thrust_mass_flow(thrust: &Vector3<f64>, isp: f64) -> f64 {
+    propellant_mass_flow(thrust.norm(), isp)
+}
+
+/// Tatsächlich wirkender Schub eines Schritts der Länge `dt`
+/// (siehe `PhysicsConstants::available_thrust`)
+pub fn available_thrust(thrust: &Vector3<f64>, isp: f64, propellant: f64, dt: f64) -> Vector3<f64> {
+    PhysicsConstants::STANDARD.available_thrust(thrust, isp, propellant, dt)
 }
 
 /// Integriert Zustand um dt (Euler-Verfahren, später RK4)
+///
+/// Die Masse fällt nicht unter `dry_mass`.
 pub fn integrate_state(
     state: &mut SpacecraftState,
     acceleration: &Vector3<f64>,
     mass_flow: f64,
+    dry_mass: f64,
     dt: f64,
 ) {
     // Geschwindigkeit aktualisieren: v = v + a*dt
@@ -125,10 +1277,7 @@ pub fn integrate_state(
     state.position += state.velocity * dt + 0.5 * acceleration * dt * dt;
 
     // Masse aktualisieren
-    state.mass -= mass_flow * dt;
-    if state.mass < 100.0 {
-        state.mass = 100.0; // Trockenmasse minimum
-    }
+    state.mass = (state.mass - mass_flow * dt).max(dry_mass.min(state.mass));
 
     state.time += dt;
 }
@@ -140,40 +1289,249 @@ pub fn integrate_rk4(
     moon_pos: &Vector3<f64>,
     thrust: &Vector3<f64>,
     isp: f64,
+    dry_mass: f64,
+    dt: f64,
+) {
+    integrate_rk4_bodies(
+        state,
+        &[GravBody::earth(*earth_pos), GravBody::moon(*moon_pos)],
+        thrust,
+        isp,
+        dry_mass,
+        dt,
+    );
+}
+
+/// RK4-Integration mit beliebigen gravitierenden Körpern
+///
+/// Reicht der Treibstoff oberhalb von `dry_mass` nicht für den ganzen
+/// Schritt, wird der Schub anteilig reduziert; bei Trockenmasse kein Schub.
+pub fn integrate_rk4_bodies(
+    state: &mut SpacecraftState,
+    bodies: &[GravBody],
+    thrust: &Vector3<f64>,
+    isp: f64,
+    dry_mass: f64,
+    dt: f64,
+) {
+    let propagator = Propagator::default();
+    integrate_bodies(state, bodies, thrust, isp, dry_mass, &propagator, dt);
+}
+
+/// Wie `integrate_rk4_bodies`, mit wählbarem Verfahren und Störkräften
+pub fn integrate_bodies(
+    state: &mut SpacecraftState,
+    bodies: &[GravBody],
+    thrust: &Vector3<f64>,
+    isp: f64,
+    dry_mass: f64,
+    propagator: &Propagator,
     dt: f64,
 ) {
-    let mass_flow = propellant_mass_flow(thrust.norm(), isp);
+    let clamp_mass = dry_mass.min(state.mass);
+    let dynamics = powered_dynamics(state, bodies, thrust, isp, dry_mass, propagator, dt);
+    match propagator.frame {
+        Some(_) => integrate_rk4_with(state, dynamics, dt),
+        None => propagator.integrator.integrate_with(state, dynamics, dt),
+    }
+    state.mass = state.mass.max(clamp_mass);
+}
+
+/// Wie `integrate_rk4_bodies`, aber im mitrotierenden System `frame`
+/// (Zustand und Körperpositionen in rotierenden Koordinaten)
+pub fn integrate_rk4_rotating(
+    state: &mut SpacecraftState,
+    bodies: &[GravBody],
+    thrust: &Vector3<f64>,
+    isp: f64,
+    dry_mass: f64,
+    frame: &RotatingFrame,
+    dt: f64,
+) {
+    let propagator = Propagator {
+        frame: Some(*frame),
+        ..Default::default()
+    };
+    integrate_bodies(state, bodies, thrust, isp, dry_mass, &propagator, dt);
+}
+
+/// Kraftmodell eines Schritts der Länge `dt` mit (auf den Treibstoff begrenztem)
+/// Schub, Gravitation nach `propagator.constants` und den Störkräften bzw.
+/// Scheinkräften des mitrotierenden Systems
+fn powered_dynamics<'a>(
+    state: &SpacecraftState,
+    bodies: &'a [GravBody],
+    thrust: &Vector3<f64>,
+    isp: f64,
+    dry_mass: f64,
+    propagator: &Propagator,
+    dt: f64,
+) -> impl Fn(&SpacecraftState) -> (Vector3<f64>, f64) + 'a {
+    let propagator = *propagator;
+    let constants = propagator.constants;
+    let thrust = constants.available_thrust(thrust, isp, state.mass - dry_mass, dt);
+    let mass_flow = constants.propellant_mass_flow(thrust.norm(), isp);
+    debug_assert!(
+        thrust.norm() > 0.0 || mass_flow == 0.0,
+        "Massenverlust ohne Schub"
+    );
+
+    move |s| {
+        let extra = match &propagator.frame {
+            Some(frame) => frame.acceleration(&s.position, &s.velocity),
+            None => {
+                let central = &bodies[propagator.central_body];
+                propagator.forces.acceleration(s, central, bodies)
+            }
+        };
+        let a = constants.gravity_acceleration_bodies(&s.position, bodies)
+            + thrust_acceleration(&thrust, s.mass)
+            + extra;
+        (a, mass_flow)
+    }
+}
+
+/// Scheinbeschleunigung im mit `omega` [rad/s] rotierenden Bezugssystem
+///
+/// a = -2 ω × v - ω × (ω × r)  (Coriolis + Zentrifugal), `position` relativ
+/// zu einem Punkt auf der Drehachse, `velocity` im rotierenden System.
+pub fn rotating_frame_acceleration(
+    position: &Vector3<f64>,
+    velocity: &Vector3<f64>,
+    omega: &Vector3<f64>,
+) -> Vector3<f64> {
+    -2.0 * omega.cross(velocity) - omega.cross(&omega.cross(position))
+}
+
+/// Mitrotierendes Bezugssystem zweier Primärkörper (z.B. Erde-Mond)
+///
+/// Koordinaten bleiben primärzentriert; die Drehachse geht durch das
+/// Baryzentrum, sodass beide Körper in Ruhe sind.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RotatingFrame {
+    /// Winkelgeschwindigkeit des Systems [rad/s]
+    pub omega: Vector3<f64>,
+    /// Punkt auf der Drehachse (Baryzentrum) [m]
+    pub axis_origin: Vector3<f64>,
+}
+
+impl RotatingFrame {
+    /// Kreisbahn des Sekundärkörpers (μ₂, Abstand entlang +x) um den Primärkörper
+    /// im Ursprung, Drehung um +z
+    pub fn two_body(primary_mu: f64, secondary_mu: f64, distance: f64) -> Self {
+        let total_mu = primary_mu + secondary_mu;
+        Self {
+            omega: Vector3::new(0.0, 0.0, (total_mu / distance.powi(3)).sqrt()),
+            axis_origin: Vector3::new(distance * secondary_mu / total_mu, 0.0, 0.0),
+        }
+    }
+
+    /// Scheinbeschleunigung am Ort `position` mit rotierender Geschwindigkeit [m/s²]
+    pub fn acceleration(&self, position: &Vector3<f64>, velocity: &Vector3<f64>) -> Vector3<f64> {
+        rotating_frame_acceleration(&(position - self.axis_origin), velocity, &self.omega)
+    }
+
+    /// Rechnet eine Geschwindigkeit relativ zum ruhenden Körper bei `body_pos`
+    /// ins rotierende System um: v_rot = v - ω × (r - r_body)
+    pub fn to_rotating_velocity(
+        &self,
+        position: &Vector3<f64>,
+        velocity: &Vector3<f64>,
+        body_pos: &Vector3<f64>,
+    ) -> Vector3<f64> {
+        velocity - self.omega.cross(&(position - body_pos))
+    }
+}
+
+/// Numerisches Integrationsverfahren der Translationsdynamik
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Integrator {
+    /// Explizites Euler-Verfahren (1. Ordnung)
+    Euler,
+    /// Klassisches Runge-Kutta-Verfahren 4. Ordnung
+    #[default]
+    Rk4,
+}
+
+impl Integrator {
+    /// Ein Schritt mit frei wählbarem Kraftmodell (siehe `integrate_rk4_with`)
+    pub fn integrate_with<F>(self, state: &mut SpacecraftState, dynamics: F, dt: f64)
+    where
+        F: Fn(&SpacecraftState) -> (Vector3<f64>, f64),
+    {
+        match self {
+            Integrator::Euler => integrate_euler_with(state, dynamics, dt),
+            Integrator::Rk4 => integrate_rk4_with(state, dynamics, dt),
+        }
+    }
+}
+
+/// Einstellungen der Bahnpropagation
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Propagator {
+    /// Integrationsverfahren
+    pub integrator: Integrator,
+    /// Störkräfte zusätzlich zur Punktmassen-Gravitation
+    pub forces: ForceModel,
+    /// Index des Zentralkörpers der Störkräfte in `bodies` (Standard: 0)
+    pub central_body: usize,
+    /// Mitrotierendes Bezugssystem (None = inertial); Zustand und Körper in
+    /// rotierenden Koordinaten, dann stets RK4 und ohne Störkräfte
+    pub frame: Option<RotatingFrame>,
+    /// Gravitationsgesetz (Singularitätsbehandlung) und g0 des Massenstroms
+    pub constants: PhysicsConstants,
+}
+
+/// Explizites Euler-Verfahren mit frei wählbarem Kraftmodell
+///
+/// Kraftmodell nur am Schrittanfang ausgewertet: r += v·dt, v += a·dt, m -= ṁ·dt.
+pub fn integrate_euler_with<F>(state: &mut SpacecraftState, dynamics: F, dt: f64)
+where
+    F: Fn(&SpacecraftState) -> (Vector3<f64>, f64),
+{
+    let (acceleration, mass_flow) = dynamics(state);
+    state.position += state.velocity * dt;
+    state.velocity += acceleration * dt;
+    state.mass -= mass_flow * dt;
+    state.time += dt;
+}
+
+/// RK4-Integration mit frei wählbarem Kraftmodell
+///
+/// `dynamics` liefert für einen (Zwischen-)Zustand die Beschleunigung [m/s²]
+/// und den Massenstrom [kg/s]. Die Stufen werden auf Kopien ausgewertet,
+/// `state` ändert sich erst am Ende des Schritts.
+pub fn integrate_rk4_with<F>(state: &mut SpacecraftState, dynamics: F, dt: f64)
+where
+    F: Fn(&SpacecraftState) -> (Vector3<f64>, f64),
+{
+    let stage = |k_vel: &Vector3<f64>, k_acc: &Vector3<f64>, k_flow: f64, h: f64| SpacecraftState {
+        position: state.position + k_vel * h,
+        velocity: state.velocity + k_acc * h,
+        mass: state.mass - k_flow * h,
+        time: state.time + h,
+    };
 
     // k1
-    let a1 = gravity_acceleration(&state.position, earth_pos, moon_pos)
-        + thrust_acceleration(thrust, state.mass);
     let v1 = state.velocity;
+    let (a1, m1) = dynamics(state);
 
     // k2
-    let pos2 = state.position + v1 * (dt / 2.0);
-    let vel2 = state.velocity + a1 * (dt / 2.0);
-    let a2 = gravity_acceleration(&pos2, earth_pos, moon_pos)
-        + thrust_acceleration(thrust, state.mass - mass_flow * dt / 2.0);
+    let s2 = stage(&v1, &a1, m1, dt / 2.0);
+    let (a2, m2) = dynamics(&s2);
 
     // k3
-    let pos3 = state.position + vel2 * (dt / 2.0);
-    let vel3 = state.velocity + a2 * (dt / 2.0);
-    let a3 = gravity_acceleration(&pos3, earth_pos, moon_pos)
-        + thrust_acceleration(thrust, state.mass - mass_flow * dt / 2.0);
+    let s3 = stage(&s2.velocity, &a2, m2, dt / 2.0);
+    let (a3, m3) = dynamics(&s3);
 
     // k4
-    let pos4 = state.position + vel3 * dt;
-    let vel4 = state.velocity + a3 * dt;
-    let a4 = gravity_acceleration(&pos4, earth_pos, moon_pos)
-        + thrust_acceleration(thrust, state.mass - mass_flow * dt);
+    let s4 = stage(&s3.velocity, &a3, m3, dt);
+    let (a4, m4) = dynamics(&s4);
 
     // Kombinieren
-    state.position += (v1 + 2.0 * vel2 + 2.0 * vel3 + vel4) * (dt / 6.0);
+    state.position += (v1 + 2.0 * s2.velocity + 2.0 * s3.velocity + s4.velocity) * (dt / 6.0);
     state.velocity += (a1 + 2.0 * a2 + 2.0 * a3 + a4) * (dt / 6.0);
-    state.mass -= mass_flow * dt;
-    if state.mass < 100.0 {
-        state.mass = 100.0;
-    }
+    state.mass -= (m1 + 2.0 * m2 + 2.0 * m3 + m4) * (dt / 6.0);
     state.time += dt;
 }
 
@@ -192,6 +1550,664 @@ mod tests {
         assert!((a.norm() - 9.8).abs() < 0.5);
     }
 
+    #[test]
+    fn test_srp_shadow_and_magnitude() {
+        // Fahrzeug auf der sonnenabgewandten Seite der Erde (Sonne auf +x)
+        let sun = Vector3::new(AU, 0.0, 0.0);
+        let earth = GravBody::earth(Vector3::zeros());
+        let night_side = Vector3::new(-(R_EARTH + 400_000.0), 0.0, 0.0);
+        let a = srp_acceleration(&night_side, &sun, 1_000.0, 10.0, 0.3, &[earth]);
+        assert_eq!(a, Vector3::zeros());
+
+        // Volle Sonne bei 1 AU: P = 1361 / c ≈ 4.54e-6 N/m²
+        let day_side = Vector3::new(R_EARTH + 400_000.0, 0.0, 0.0);
+        let position = sun + Vector3::new(0.0, AU, 0.0);
+        let a = srp_acceleration(&position, &sun, 1_000.0, 10.0, 0.3, &[]);
+        assert!(
+            (a.norm() - 4.54e-6 * 1.3 * 10.0 / 1_000.0).abs() < 1e-10,
+            "{}",
+            a.norm()
+        );
+        assert!(a.normalize().dot(&Vector3::y()) > 0.999_999);
+
+        // Im Kraftmodell der Propagation enthalten, wenn aktiviert
+        let start = SpacecraftState::new(day_side, Vector3::new(0.0, 7_670.0, 0.0), 1_000.0);
+        let propagate = |srp| {
+            let mut state = start.clone();
+            let propagator = Propagator {
+                forces: ForceModel {
+                    srp,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            integrate_bodies(
+                &mut state,
+                &[earth],
+                &Vector3::zeros(),
+                300.0,
+                0.0,
+                &propagator,
+                10.0,
+            );
+            state.position
+        };
+        let srp = SolarRadiationPressure {
+            area: 10.0,
+            reflectivity: 0.3,
+        };
+        let shift = (propagate(Some(srp)) - propagate(None)).norm();
+        assert!((shift - 0.5 * 5.9e-8 * 100.0).abs() < 1e-7, "{}", shift);
+    }
+
+    #[test]
+    fn test_force_model_toggles() {
+        // Niedrige Bahn auf der Tagseite: Luftwiderstand und Strahlungsdruck wären wirksam
+        let earth = GravBody::earth(Vector3::zeros());
+        let position = Vector3::new(R_EARTH + 200_000.0, 0.0, 1_000_000.0);
+        let state = SpacecraftState::new(position, Vector3::new(0.0, 7_800.0, 0.0), 1_000.0);
+        let bodies = [earth];
+        assert_eq!(
+            ForceModel::default().acceleration(&state, &earth, &bodies),
+            Vector3::zeros()
+        );
+
+        let j2_only = ForceModel {
+            j2: true,
+            ..Default::default()
+        };
+        let a = j2_only.acceleration(&state, &earth, &bodies);
+        let expected = j2_acceleration(&position, &earth, J2_EARTH);
+        assert!((a - expected).norm() <= 1e-12 * expected.norm(), "{:?}", a);
+        // Größenordnung 1e-3 · g nahe der Erdoberfläche
+        assert!(a.norm() > 1e-3 && a.norm() < 0.1, "{}", a.norm());
+
+        let drag = ForceModel {
+            drag: Some(AtmosphericDrag {
+                area: 10.0,
+                drag_coefficient: 2.2,
+            }),
+            ..Default::default()
+        };
+        let a = drag.acceleration(&state, &earth, &bodies);
+        assert!(a.norm() > 0.0 && a.dot(&state.velocity) < 0.0);
+    }
+
+    #[test]
+    fn test_moon_ephemeris_interpolation() {
+        let a = Vector3::new(EARTH_MOON_DISTANCE, 0.0, 0.0);
+        let b = Vector3::new(EARTH_MOON_DISTANCE * 0.99, 3.6e7, 1.0e6);
+        let ephemeris = MoonEphemeris::new(vec![(3_600.0, b), (0.0, a)]).unwrap();
+
+        assert_eq!(ephemeris.position_at(1_800.0), (a + b) / 2.0);
+        assert_eq!(ephemeris.position_at(0.0), a);
+        assert_eq!(ephemeris.position_at(-10.0), a);
+        assert_eq!(ephemeris.position_at(7_200.0), b);
+        assert_eq!(ephemeris.velocity_at(1_800.0), (b - a) / 3_600.0);
+        assert_eq!(ephemeris.velocity_at(7_200.0), Vector3::zeros());
+        assert!(MoonEphemeris::new(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn test_state_is_finite() {
+        let mut state =
+            SpacecraftState::new(Vector3::new(R_EARTH, 0.0, 0.0), Vector3::zeros(), 1.0);
+        assert!(state.is_finite());
+        state.velocity.y = f64::NAN;
+        assert!(!state.is_finite());
+        state.velocity.y = 0.0;
+        state.mass = f64::INFINITY;
+        assert!(!state.is_finite());
+    }
+
+    #[test]
+    fn test_altitude_and_speed() {
+        let state = SpacecraftState::new(
+            Vector3::new(R_EARTH + 400_000.0, 0.0, 0.0),
+            Vector3::new(0.0, 3.0, 4.0),
+            1_000.0,
+        );
+        assert!((state.altitude_above(&Vector3::zeros(), R_EARTH) - 400_000.0).abs() < 1e-6);
+        assert_eq!(state.speed(), 5.0);
+    }
+
+    #[test]
+    fn test_zero_thrust_keeps_mass() {
+        let bodies = [GravBody::earth(Vector3::zeros())];
+        let r = R_EARTH + 400_000.0;
+        let mut state = SpacecraftState::new(
+            Vector3::new(r, 0.0, 0.0),
+            Vector3::new(0.0, (bodies[0].mu / r).sqrt(), 0.0),
+            12_345.678,
+        );
+
+        for _ in 0..5_000 {
+            integrate_rk4_bodies(
+                &mut state,
+                &bodies,
+                &Vector3::new(0.0, -0.0, 0.0),
+                450.0,
+                0.0,
+                1.0,
+            );
+        }
+        assert_eq!(state.mass, 12_345.678);
+        assert_eq!(thrust_mass_flow(&Vector3::zeros(), 450.0), 0.0);
+    }
+
+    #[test]
+    fn test_mass_clamped_to_dry_mass() {
+        let bodies = [GravBody::earth(Vector3::zeros())];
+        let dry_mass = 5_000.0;
+        let mut state = SpacecraftState::new(
+            Vector3::new(R_EARTH + 400_000.0, 0.0, 0.0),
+            Vector3::zeros(),
+            5_100.0,
+        );
+        let thrust = Vector3::new(100_000.0, 0.0, 0.0);
+
+        // 100 kg Treibstoff bei ~22.7 kg/s: im fünften Schritt erschöpft
+        let mut speeds = Vec::new();
+        for _ in 0..10 {
+            integrate_rk4_bodies(&mut state, &bodies, &thrust, 450.0, dry_mass, 1.0);
+            assert!(state.mass >= dry_mass);
+            speeds.push(state.velocity.x);
+        }
+        assert_eq!(state.mass, dry_mass);
+        // Ohne Treibstoff keine Schubbeschleunigung mehr (nur Gravitation)
+        assert!((speeds[9] - speeds[8]) < 0.0);
+    }
+
+    #[test]
+    fn test_rk4_with_constant_acceleration() {
+        let r0 = Vector3::new(1.0, 2.0, 3.0);
+        let v0 = Vector3::new(10.0, 0.0, -5.0);
+        let a = Vector3::new(0.5, -1.5, 2.0);
+        let mut state = SpacecraftState::new(r0, v0, 1_000.0);
+
+        let dt = 0.5;
+        for _ in 0..20 {
+            integrate_rk4_with(&mut state, |_| (a, 2.0), dt);
+        }
+
+        let t = state.time;
+        assert_eq!(t, 10.0);
+        let expected = r0 + v0 * t + 0.5 * a * t * t;
+        assert!((state.position - expected).norm() < 1e-9);
+        assert!((state.velocity - (v0 + a * t)).norm() < 1e-9);
+        assert!((state.mass - 980.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_patched_conic_matches_integration() {
+        let earth = GravBody::earth(Vector3::zeros());
+        let r_park = R_EARTH + 200_000.0;
+        let estimate = patched_conic_transfer(
+            r_park,
+            R_MOON + 100_000.0,
+            earth.mu,
+            G * M_MOON,
+            MOON_SOI_RADIUS,
+        );
+        // Bekannte Größenordnung: TLI ≈ 3.1 km/s, bis zur Einflusssphäre ≈ 2-3 Tage
+        assert!((estimate.tli_delta_v - 3_130.0).abs() < 50.0);
+        assert!(estimate.coast_time > 2.0 * 86_400.0 && estimate.coast_time < 3.0 * 86_400.0);
+        assert!(estimate.loi_delta_v > 0.0);
+
+        // Impulsiver TLI, Mond gegenüber dem Apogäum: Apogäum erreicht die Mondbahn
+        let bodies = [
+            earth,
+            GravBody::moon(Vector3::new(EARTH_MOON_DISTANCE, 0.0, 0.0)),
+        ];
+        let v_park = (earth.mu / r_park).sqrt();
+        let mut state = SpacecraftState::new(
+            Vector3::new(r_park, 0.0, 0.0),
+            Vector3::new(0.0, v_park + estimate.tli_delta_v, 0.0),
+            1_000.0,
+        );
+        let r_soi = EARTH_MOON_DISTANCE - MOON_SOI_RADIUS;
+        let mut soi_time = None;
+        let mut max_radius: f64 = 0.0;
+        while state.time < 6.0 * 86_400.0 {
+            integrate_rk4_bodies(&mut state, &bodies, &Vector3::zeros(), 300.0, 0.0, 60.0);
+            max_radius = max_radius.max(state.position.norm());
+            if soi_time.is_none() && state.position.norm() >= r_soi {
+                soi_time = Some(state.time);
+            }
+        }
+
+        assert!((max_radius - EARTH_MOON_DISTANCE).abs() / EARTH_MOON_DISTANCE < 0.03);
+        let soi_time = soi_time.expect("Einflusssphäre nicht erreicht");
+        assert!((soi_time - estimate.coast_time).abs() / estimate.coast_time < 0.03);
+    }
+
+    #[test]
+    fn test_gravitational_potential() {
+        let bodies = [GravBody::earth(Vector3::zeros())];
+        let potential = |r: f64| gravitational_potential(&Vector3::new(r, 0.0, 0.0), &bodies);
+
+        assert!(potential(R_EARTH) < potential(2.0 * R_EARTH));
+        assert!((potential(R_EARTH) - 2.0 * potential(2.0 * R_EARTH)).abs() < 1e-6);
+        assert!(potential(1e20).abs() < 1e-3);
+        assert!(potential(1e20) < 0.0);
+    }
+
+    #[test]
+    fn test_lagrange_points() {
+        let d = EARTH_MOON_DISTANCE;
+        let mu = M_MOON / (M_EARTH + M_MOON);
+        let earth = Vector3::new(-mu * d, 0.0, 0.0);
+        let moon = Vector3::new((1.0 - mu) * d, 0.0, 0.0);
+        let [l1, l2, l3, l4, l5] = lagrange_points(M_EARTH, M_MOON, d);
+
+        // L4/L5: gleichseitige Dreiecke mit Erde und Mond
+        for l in [l4, l5] {
+            assert!(((l - earth).norm() - d).abs() < 1e-3);
+            assert!(((l - moon).norm() - d).abs() < 1e-3);
+        }
+
+        // L1 zwischen den Primärkörpern, ca. 326 000 km von der Erde
+        assert!(l1.x > earth.x && l1.x < moon.x);
+        assert!(((l1 - earth).norm() - 326_000_000.0).abs() < 3_000_000.0);
+        // L2 hinter dem Mond, L3 auf der Gegenseite der Erde
+        assert!(l2.x > moon.x);
+        assert!(l3.x < earth.x);
+    }
+
+    #[test]
+    fn test_remaining_delta_v() {
+        let mut state = SpacecraftState::new(Vector3::zeros(), Vector3::zeros(), 45_000.0);
+        let full = state.remaining_delta_v(5_000.0, 450.0);
+        assert!((full - 450.0 * G0 * 9.0_f64.ln()).abs() < 1e-9);
+
+        state.mass = 20_000.0;
+        assert!(state.remaining_delta_v(5_000.0, 450.0) < full);
+        state.mass = 5_000.0;
+        assert_eq!(state.remaining_delta_v(5_000.0, 450.0), 0.0);
+    }
+
+    #[test]
+    fn test_free_return_classification() {
+        let earth = GravBody::earth(Vector3::zeros());
+        let moon = GravBody::moon(Vector3::new(EARTH_MOON_DISTANCE, 0.0, 0.0));
+        let bodies = [earth, moon];
+
+        // Hochelliptischer Bogen (Perigäum 80 km, Apogäum 100 000 km), vom Mond weg
+        let r_perigee = R_EARTH + 80_000.0;
+        let r_apogee = 1.0e8;
+        let a = 0.5 * (r_perigee + r_apogee);
+        let v_perigee = (earth.mu * (2.0 / r_perigee - 1.0 / a)).sqrt();
+        let returning = SpacecraftState::new(
+            Vector3::new(-r_perigee, 0.0, 0.0),
+            Vector3::new(0.0, v_perigee, 0.0),
+            1_000.0,
+        );
+        assert!(is_free_return(&returning, &bodies, 3.0 * 86_400.0));
+
+        // Hyperbolischer Fluchtbogen
+        let escape_speed = (2.0 * earth.mu / r_perigee).sqrt();
+        let escaping = SpacecraftState::new(
+            Vector3::new(-r_perigee, 0.0, 0.0),
+            Vector3::new(0.0, 1.1 * escape_speed, 0.0),
+            1_000.0,
+        );
+        assert!(!is_free_return(&escaping, &bodies, 3.0 * 86_400.0));
+    }
+
+    #[test]
+    fn test_closest_approach_on_lunar_arc() {
+        // Antriebsloser Anflug knapp am Mond vorbei
+        let moon = Vector3::new(EARTH_MOON_DISTANCE, 0.0, 0.0);
+        let state = SpacecraftState::new(
+            moon + Vector3::new(-5.0e7, 5.0e6, 0.0),
+            Vector3::new(1_500.0, 0.0, 0.0),
+            1_000.0,
+        );
+        let start_distance = (state.position - moon).norm();
+
+        let (distance, time) = closest_approach(&state, |_| moon, 3.0 * 86_400.0);
+        assert!(distance < start_distance, "{} m", distance);
+        assert!(time > 0.0 && time < 3.0 * 86_400.0, "{} s", time);
+
+        // Vom Mond weg: größte Annäherung ist der Startpunkt
+        let receding = SpacecraftState {
+            velocity: -state.velocity,
+            ..state.clone()
+        };
+        assert_eq!(
+            closest_approach(&receding, |_| moon, 86_400.0),
+            (start_distance, 0.0)
+        );
+
+        // Bahn durch die Erde: Suche endet beim Aufschlag auf der Erde
+        let through_earth = SpacecraftState::new(
+            Vector3::new(-2.0e7, 0.0, 0.0),
+            Vector3::new(5_000.0, 0.0, 0.0),
+            1_000.0,
+        );
+        let (distance, time) = closest_approach(&through_earth, |_| moon, 3.0 * 86_400.0);
+        assert!(
+            distance > EARTH_MOON_DISTANCE + 0.99 * R_EARTH,
+            "{} m",
+            distance
+        );
+        assert!(time < 3_600.0, "{} s", time);
+    }
+
+    #[test]
+    fn test_flight_path_angle() {
+        let earth = Vector3::new(1.0e6, 0.0, 0.0);
+        let pos = earth + Vector3::new(0.0, R_EARTH, 0.0);
+
+        let up = flight_path_angle(&pos, &Vector3::new(0.0, 100.0, 0.0), &earth);
+        let down = flight_path_angle(&pos, &Vector3::new(0.0, -100.0, 0.0), &earth);
+        let tangential = flight_path_angle(&pos, &Vector3::new(7_800.0, 0.0, 50.0), &earth);
+        let climbing = flight_path_angle(&pos, &Vector3::new(100.0, 100.0, 0.0), &earth);
+
+        assert!((up - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert!((down + std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert!(tangential.abs() < 1e-12);
+        assert!((climbing - std::f64::consts::FRAC_PI_4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rotating_frame_acceleration() {
+        let omega = Vector3::new(0.0, 0.0, 2.0);
+        // Zentrifugal radial nach außen: ω² r
+        let centrifugal =
+            rotating_frame_acceleration(&Vector3::new(3.0, 0.0, 0.0), &Vector3::zeros(), &omega);
+        assert!((centrifugal - Vector3::new(12.0, 0.0, 0.0)).norm() < 1e-12);
+        // Coriolis senkrecht zur Bewegung: -2 ω × v
+        let coriolis =
+            rotating_frame_acceleration(&Vector3::zeros(), &Vector3::new(1.0, 0.0, 0.0), &omega);
+        assert!((coriolis - Vector3::new(0.0, -4.0, 0.0)).norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_rotating_frame_l4_stays() {
+        let d = EARTH_MOON_DISTANCE;
+        let bodies = [
+            GravBody::earth(Vector3::zeros()),
+            GravBody::moon(Vector3::new(d, 0.0, 0.0)),
+        ];
+        let frame = RotatingFrame::two_body(bodies[0].mu, bodies[1].mu, d);
+        let l4 = Vector3::new(d / 2.0, d * 3f64.sqrt() / 2.0, 0.0);
+
+        // 30 Tage antriebslos am Lagrange-Punkt L4
+        let mut state = SpacecraftState::new(l4, Vector3::zeros(), 1_000.0);
+        for _ in 0..(30 * 144) {
+            integrate_rk4_rotating(
+                &mut state,
+                &bodies,
+                &Vector3::zeros(),
+                300.0,
+                0.0,
+                &frame,
+                600.0,
+            );
+        }
+        assert!((state.position - l4).norm() < 1.0e-3 * d);
+    }
+
+    #[test]
+    fn test_state_json_roundtrip() {
+        let mut state = SpacecraftState::new(
+            Vector3::new(6_578_137.123456789, -1.0e-7, 0.1 + 0.2),
+            Vector3::new(-7_784.261_5, 1.0 / 3.0, f64::MIN_POSITIVE),
+            45_000.000_000_1,
+        );
+        state.time = 12_345.678_9;
+
+        let restored = SpacecraftState::from_json(&state.to_json()).unwrap();
+        for i in 0..3 {
+            assert_eq!(restored.position[i].to_bits(), state.position[i].to_bits());
+            assert_eq!(restored.velocity[i].to_bits(), state.velocity[i].to_bits());
+        }
+        assert_eq!(restored.mass.to_bits(), state.mass.to_bits());
+        assert_eq!(restored.time.to_bits(), state.time.to_bits());
+        assert!(SpacecraftState::from_json("{\"mass\": 1.0}").is_err());
+    }
+
+    #[test]
+    fn test_leo_orbital_period() {
+        let earth = GravBody::earth(Vector3::zeros());
+        let r = R_EARTH + 400_000.0;
+        let state = SpacecraftState::new(
+            Vector3::new(r, 0.0, 0.0),
+            Vector3::new(0.0, (earth.mu / r).sqrt(), 0.0),
+            1_000.0,
+        );
+
+        let a = semi_major_axis(&state, &earth);
+        assert!((a - r).abs() < 1e-3);
+        let period_minutes = orbital_period(a, earth.mu) / 60.0;
+        assert!(
+            (period_minutes - 92.4).abs() < 0.2,
+            "{} min",
+            period_minutes
+        );
+        let revolution = mean_motion(a, earth.mu) * orbital_period(a, earth.mu);
+        assert!((revolution - 2.0 * std::f64::consts::PI).abs() < 1e-12);
+        assert_eq!(orbital_period(-a, earth.mu), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_escape_trajectory() {
+        let earth = GravBody::earth(Vector3::zeros());
+        let r = 2.0 * EARTH_MOON_DISTANCE;
+        let escape_speed = (2.0 * earth.mu / r).sqrt();
+        let state = |speed: f64, radial: f64| {
+            SpacecraftState::new(
+                Vector3::new(-r, 0.0, 0.0),
+                Vector3::new(-radial, speed, 0.0),
+                1_000.0,
+            )
+        };
+        let beyond_moon = EARTH_MOON_DISTANCE + MOON_SOI_RADIUS;
+
+        assert!(specific_energy(&state(1.1 * escape_speed, 0.0), &earth) > 0.0);
+        assert!(is_escape_trajectory(
+            &state(1.1 * escape_speed, 10.0),
+            &earth,
+            beyond_moon
+        ));
+        // Gebunden, auf die Erde zu oder noch innerhalb der Mondbahn
+        assert!(!is_escape_trajectory(
+            &state(0.9 * escape_speed, 10.0),
+            &earth,
+            beyond_moon
+        ));
+        assert!(!is_escape_trajectory(
+            &state(1.1 * escape_speed, -10.0),
+            &earth,
+            beyond_moon
+        ));
+        assert!(!is_escape_trajectory(
+            &state(1.1 * escape_speed, 10.0),
+            &earth,
+            3.0 * r
+        ));
+    }
+
+    #[test]
+    fn test_environment_radiation_belts() {
+        let sun = sun_position(0.0);
+        let dose = |altitude: f64| {
+            let position = Vector3::new(0.0, R_EARTH + altitude, 0.0);
+            environment_at(&position, 0.0, &sun).2
+        };
+
+        // Maximum beim Durchflug des inneren Gürtels um 5000 km
+        let profile: Vec<f32> = (0..=40).map(|i| dose(i as f64 * 500_000.0)).collect();
+        let peak = profile
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap()
+            .0;
+        assert_eq!(peak, 10);
+        assert!(dose(EARTH_MOON_DISTANCE) < 0.05 * dose(5_000_000.0));
+
+        // Vakuum und Erdschatten
+        let (sunlit, pressure, _) =
+            environment_at(&Vector3::new(R_EARTH + 400_000.0, 0.0, 0.0), 0.0, &sun);
+        let (shadow, _, _) =
+            environment_at(&Vector3::new(-R_EARTH - 400_000.0, 0.0, 0.0), 0.0, &sun);
+        assert!(pressure < 1e-15);
+        assert!(sunlit > shadow);
+    }
+
+    #[test]
+    fn test_predict_impact_free_fall() {
+        let moon = GravBody::moon(Vector3::zeros());
+        let r0 = R_MOON + 10_000.0;
+        let state = SpacecraftState::new(Vector3::new(0.0, 0.0, r0), Vector3::zeros(), 1_000.0);
+
+        let (time, position) = predict_impact(&state, &[moon], &moon).unwrap();
+
+        // Radialer Fall aus der Ruhe im 1/r²-Feld:
+        // t = sqrt(r0³ / 2μ) * (sqrt(x(1-x)) + acos(sqrt(x))),  x = R / r0
+        let x = R_MOON / r0;
+        let analytic =
+            (r0.powi(3) / (2.0 * moon.mu)).sqrt() * ((x * (1.0 - x)).sqrt() + x.sqrt().acos());
+        assert!(
+            (time - analytic).abs() < 0.05,
+            "{} statt {}",
+            time,
+            analytic
+        );
+        assert!((position.z - R_MOON).abs() < 1.0);
+
+        // Kreisbahn: kein Aufschlag
+        let orbit = SpacecraftState::new(
+            Vector3::new(r0, 0.0, 0.0),
+            Vector3::new(0.0, (moon.mu / r0).sqrt(), 0.0),
+            1_000.0,
+        );
+        assert!(predict_impact(&orbit, &[moon], &moon).is_none());
+    }
+
+    #[test]
+    fn test_energy_conserved_on_coast() {
+        let bodies = [
+            GravBody::earth(Vector3::zeros()),
+            GravBody::moon(Vector3::new(EARTH_MOON_DISTANCE, 0.0, 0.0)),
+        ];
+        let r = R_EARTH + 400_000.0;
+        let v = (bodies[0].mu / r).sqrt() * 1.1; // leicht elliptisch
+        let mut state = SpacecraftState::new(
+            Vector3::new(r, 0.0, 0.0),
+            Vector3::new(0.0, v, 0.0),
+            1_000.0,
+        );
+
+        let initial = total_specific_energy(&state, &bodies);
+        for _ in 0..6_000 {
+            integrate_rk4_bodies(&mut state, &bodies, &Vector3::zeros(), 300.0, 0.0, 1.0);
+        }
+        let drift = (total_specific_energy(&state, &bodies) - initial) / initial;
+        assert!(drift.abs() < 1e-9, "Energiedrift {:e}", drift);
+    }
+
+    #[test]
+    fn test_unit_constants() {
+        let unit = PhysicsConstants {
+            g: 1.0,
+            m_earth: 1.0,
+            m_moon: 0.0,
+            ..Default::default()
+        };
+        let spacecraft = Vector3::new(2.0, 0.0, 0.0);
+        let far_away = Vector3::new(1e9, 0.0, 0.0);
+
+        let a = unit.gravity_acceleration(&spacecraft, &Vector3::zeros(), &far_away);
+        assert!((a - Vector3::new(-0.25, 0.0, 0.0)).norm() < 1e-12);
+
+        let f = unit.gravitational_force(&spacecraft, 1.0, &Vector3::zeros(), 1.0);
+        assert!((f.norm() - 0.25).abs() < 1e-12);
+
+        // Singularitätsgrenze wie bisher: bei genau 1 m keine Gravitation
+        let boundary = Vector3::new(1.0, 0.0, 0.0);
+        let a = unit.gravity_acceleration(&boundary, &Vector3::zeros(), &far_away);
+        assert_eq!(a, Vector3::zeros());
+    }
+
+    #[test]
+    fn test_plummer_softening() {
+        let plummer = PhysicsConstants {
+            softening: GravitySoftening::Plummer(10.0),
+            ..Default::default()
+        };
+        let body = GravBody {
+            name: "Point",
+            position: Vector3::zeros(),
+            mu: 1.0e6,
+            radius: 0.0,
+            terrain_height: flat_terrain,
+        };
+        let accel = |x: f64| {
+            plummer
+                .gravity_acceleration_bodies(&Vector3::new(x, 0.0, 0.0), &[body])
+                .x
+        };
+
+        // Endlich und stetig durch den Mittelpunkt (Vorzeichenwechsel, kein Sprung)
+        let peak = 1.0e6 / (10.0 * 10.0);
+        let mut previous = accel(-50.0);
+        for i in -499..=500 {
+            let current = accel(i as f64 * 0.1);
+            assert!(current.is_finite() && current.abs() < peak);
+            assert!((current - previous).abs() < 0.1 * peak);
+            previous = current;
+        }
+        assert_eq!(accel(0.0), 0.0);
+
+        // Fernfeld entspricht Newton, Cutoff springt dagegen auf null
+        let newton = -1.0e6 / 1.0e8;
+        assert!(((accel(1.0e4) - newton) / newton).abs() < 1e-5);
+        let cutoff = GravitySoftening::Cutoff(1.0);
+        assert_eq!(
+            cutoff.acceleration(&Vector3::new(0.5, 0.0, 0.0), 1.0e6),
+            Vector3::zeros()
+        );
+
+        // Potential endlich im Mittelpunkt, Gradient gleich der Beschleunigung
+        let potential =
+            |x: f64| plummer.gravitational_potential(&Vector3::new(x, 0.0, 0.0), &[body]);
+        assert_eq!(potential(0.0), -1.0e6 / 10.0);
+        for x in [-30.0, -5.0, 0.5, 5.0, 30.0] {
+            let h = 1e-4;
+            let gradient = (potential(x + h) - potential(x - h)) / (2.0 * h);
+            assert!((-gradient - accel(x)).abs() < 1e-6 * peak, "x = {}", x);
+        }
+    }
+
+    #[test]
+    fn test_gravity_gradient_finite_difference() {
+        let bodies = [
+            GravBody::earth(Vector3::zeros()),
+            GravBody::moon(Vector3::new(EARTH_MOON_DISTANCE, 0.0, 0.0)),
+        ];
+        let pos = Vector3::new(7.0e6, 2.0e6, -1.0e6);
+        let gradient = gravity_gradient(&pos, &bodies);
+
+        // Zentrale Differenzen
+        let h = 10.0;
+        for j in 0..3 {
+            let mut dp = Vector3::zeros();
+            dp[j] = h;
+            let column = (gravity_acceleration_bodies(&(pos + dp), &bodies)
+                - gravity_acceleration_bodies(&(pos - dp), &bodies))
+                / (2.0 * h);
+            for i in 0..3 {
+                assert!((gradient[(i, j)] - column[i]).abs() < 1e-9);
+            }
+        }
+
+        // Laplace: Spur verschwindet im freien Raum
+        assert!(gradient.trace().abs() < 1e-15);
+    }
+
     #[test]
     fn test_mass_flow() {
         let thrust = 100_000.0; // 100 kN
@@ -200,4 +2216,111 @@ mod tests {
         // ṁ = 100000 / (300 * 9.80665) ≈ 34 kg/s
         assert!((mdot - 34.0).abs() < 1.0);
     }
+
+    #[test]
+    fn test_ground_track_rotation() {
+        let position = Vector3::new(R_EARTH + 400_000.0, 0.0, 0.0);
+        let (lat0, lon0) = ground_track(&position, 0.0, EARTH_ROTATION_RATE);
+        assert!(lat0.abs() < 1e-12 && lon0.abs() < 1e-12);
+
+        // Nach einem Viertel siderischen Tages hat sich die Erde um 90°
+        // unter dem (inertial ruhenden) Punkt hindurchgedreht
+        let quarter_day = std::f64::consts::FRAC_PI_2 / EARTH_ROTATION_RATE;
+        let (lat, lon) = ground_track(&position, quarter_day, EARTH_ROTATION_RATE);
+        assert!(lat.abs() < 1e-12);
+        assert!(((lon0 - lon) - 90.0).abs() < 1e-9);
+
+        let pole = Vector3::new(0.0, 0.0, R_EARTH);
+        assert!((ground_track(&pole, 0.0, EARTH_ROTATION_RATE).0 - 90.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_vertical_impact() {
+        let earth = Vector3::zeros();
+        let moon = Vector3::new(EARTH_MOON_DISTANCE, 0.0, 0.0);
+        let bodies = [GravBody::earth(earth), GravBody::moon(moon)];
+
+        // Senkrechter Fall auf den Mond aus 1 km Höhe mit 100 m/s
+        let start = moon - Vector3::new(R_MOON + 1_000.0, 0.0, 0.0);
+        let mut state = SpacecraftState::new(start, Vector3::new(100.0, 0.0, 0.0), 1_000.0);
+
+        let dt = 0.1;
+        let event = loop {
+            integrate_rk4(&mut state, &earth, &moon, &Vector3::zeros(), 300.0, 0.0, dt);
+            if let Some(event) = check_collision(&state, &bodies) {
+                break event;
+            }
+            assert!(state.time < 60.0, "Kollision nicht erkannt");
+        };
+
+        assert_eq!(event.body, "Moon");
+        // Erkannt im ersten Schritt unter der Oberfläche
+        assert!(event.altitude <= 0.0 && event.altitude > -event.impact_speed * dt);
+        assert!(event.impact_speed > 100.0);
+    }
+
+    #[test]
+    fn test_surface_contact_within_step() {
+        let bodies = [GravBody::moon(Vector3::zeros())];
+        // Sehne 5 km unter der Oberfläche: beide Endpunkte liegen darüber
+        let depth = 5_000.0;
+        let velocity = Vector3::new(2_000.0, 0.0, 0.0);
+        let previous = SpacecraftState::new(
+            Vector3::new(-200_000.0, R_MOON - depth, 0.0),
+            velocity,
+            1_000.0,
+        );
+        let current = SpacecraftState {
+            position: Vector3::new(200_000.0, R_MOON - depth, 0.0),
+            time: 200.0,
+            mass: 990.0,
+            ..previous.clone()
+        };
+        assert!(check_collision(&previous, &bodies).is_none());
+        assert!(check_collision(&current, &bodies).is_none());
+
+        let contact = find_surface_contact(&previous, &current, &bodies).expect("Durchflug");
+        let altitude = contact.position.norm() - R_MOON;
+        assert!(altitude <= 0.0 && altitude > -1e-3, "{} m", altitude);
+        let x = -(R_MOON.powi(2) - (R_MOON - depth).powi(2)).sqrt();
+        let time = (x + 200_000.0) / velocity.x;
+        assert!((contact.time - time).abs() < 1e-3, "{} s", contact.time);
+        assert!((contact.velocity - velocity).norm() < 1e-9);
+        assert!(contact.mass < 1_000.0 && contact.mass > 990.0);
+
+        // Bereits auf der Oberfläche: kein neuer Kontakt
+        assert!(find_surface_contact(&contact, &current, &bodies).is_none());
+    }
+
+    #[test]
+    fn test_collision_on_terrain() {
+        let crater_rim = |_latitude: f64, _longitude: f64| 500.0;
+        let flat = GravBody::moon(Vector3::zeros());
+        let rim = GravBody {
+            terrain_height: crater_rim,
+            ..flat
+        };
+        let state = SpacecraftState::new(
+            Vector3::new(R_MOON + 300.0, 0.0, 0.0),
+            Vector3::new(-10.0, 0.0, 0.0),
+            1_000.0,
+        );
+
+        // 300 m über dem Referenzradius, aber 200 m unter dem Kraterrand
+        assert!(check_collision(&state, &[flat]).is_none());
+        let event = check_collision(&state, &[rim]).expect("Kontakt mit dem Gelände");
+        assert!((event.altitude + 200.0).abs() < 1e-6);
+
+        // Kontaktsuche im Schritt endet auf dem Gelände, nicht auf dem Referenzradius
+        let previous = SpacecraftState {
+            position: Vector3::new(R_MOON + 1_000.0, 0.0, 0.0),
+            ..state.clone()
+        };
+        let current = SpacecraftState {
+            time: 100.0,
+            ..state
+        };
+        let contact = find_surface_contact(&previous, &current, &[rim]).unwrap();
+        assert!((contact.position.x - (R_MOON + 500.0)).abs() < 1e-2);
+    }
 }
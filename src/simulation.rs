@@ -6,14 +6,73 @@
 //! - Echtzeit-Telemetrie
 
 use crate::physics::{
-    self, SpacecraftState, EARTH_MOON_DISTANCE, G0,
+    self, ForceModel, GravBody, Integrator, MoonEphemeris, PhysicsConstants, Propagator,
+    RotatingFrame, SpacecraftState, TargetBody, R_EARTH,
 };
-use crate::gnc::{GuidanceComputer, KalmanFilter, MissionPhase, add_sensor_noise};
-use crate::fdir::FDIRManager;
-use crate::telemetry::{TelemetryLogger, SubsystemId};
-use nalgebra::{Vector3, Vector6};
+use crate::gnc::{
+    add_sensor_noise, AttitudeController, BurnSchedule, EngineGimbal, Guidance,
+    GuidanceComputer, KalmanError, KalmanFilter, LandingOutcome, MissionPhase, RcsCluster,
+    SOFT_LANDING_SPEED,
+};
+use crate::fdir::{FDIRManager, RedundantSubsystem};
+use crate::telemetry::{
+    EventSeverity, MilestoneKind, MissionLogger, NullLogger, ReferenceFrame, StdoutLogger,
+    SubsystemId, TelemetryLogger, Timeline,
+};
+use nalgebra::{Matrix3, Matrix6, Rotation3, Vector3, Vector6};
+use rand::{rngs::StdRng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Breite des gleichverteilten Positions-Sensorrauschens [m]
+const SENSOR_NOISE: f64 = 100.0;
+
+/// Vom Kalman-Filter nicht modellierte Beschleunigung [m/s²]
+const UNMODELED_ACCEL: f64 = 12.0;
+
+/// Integrationsschritt der Lagedynamik [s]
+const ATTITUDE_DT: f64 = 0.1;
+
+/// Redundante IMU-Subsysteme (je Positionsachse drei Kanäle) in der FDIR
+const IMU_AXES: [&str; 3] = ["IMU-X", "IMU-Y", "IMU-Z"];
+
+/// Redundante Flugrechner (je Achse des Schubkommandos drei Kanäle) in der FDIR
+const FLIGHT_COMPUTER_AXES: [&str; 3] = ["FC-X", "FC-Y", "FC-Z"];
+
+/// Zulässige Abweichung der Flugrechner-Kanäle [N] (Rundung zwischen Rechnern)
+const FLIGHT_COMPUTER_TOLERANCE: f64 = 1.0;
+
+/// Maximale Schwenkung des Haupttriebwerks [rad]
+const GIMBAL_MAX_ANGLE: f64 = 0.1;
+
+/// Lageregler: Moment je Winkelfehler [N·m/rad] (ausgelegt auf ~2 rad/s² je
+/// rad beim Standardfahrzeug mit vollen Tanks)
+const ATTITUDE_KP: f64 = 4.0e5;
+
+/// Lageregler: Dämpfungsmoment je Drehrate [N·m·s/rad]
+const ATTITUDE_KD: f64 = 2.0e5;
+
+/// Maximales Stellmoment der Lageregelung ohne Triebwerksschwenkung [N·m]
+const ATTITUDE_MAX_TORQUE: f64 = 20_000.0;
+
+/// Schub je RCS-Düse [N]: Kräftepaare am Fahrzeugradius liefern
+/// ATTITUDE_MAX_TORQUE je Achse
+const RCS_JET_THRUST: f64 = ATTITUDE_MAX_TORQUE / (4.0 * physics::VEHICLE_RADIUS);
+
+/// Schwenkrate der Soll-Lage bei einem Wechsel der Schubrichtung [rad/s]
+const ATTITUDE_SLEW_RATE: f64 = 0.05;
 
 /// Simulationsparameter
+///
+/// Als TOML-Missionsprofil ladbar, fehlende Felder nehmen den Standardwert an,
+/// unbekannte Felder (z.B. Tippfehler) sind ein Fehler.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct SimConfig {
     /// Zeitschritt [s]
     pub dt: f64,
@@ -29,6 +88,71 @@ pub struct SimConfig {
     pub dry_mass: f64,
     /// Telemetrie-Intervall [s]
     pub telemetry_interval: f64,
+    /// Trajektorie im Telemetrie-Intervall aufzeichnen
+    pub record_trajectory: bool,
+    /// Navigationstelemetrie während LOI und Abstieg mondzentriert (sonst erdzentriert)
+    pub lunar_frame_telemetry: bool,
+    /// Keine Konsolenausgabe (NullLogger statt StdoutLogger)
+    pub quiet: bool,
+    /// Höhe der kreisförmigen Startbahn über der Erdoberfläche [m]
+    pub start_altitude: f64,
+    /// Inklination der Startbahn gegen die Erde-Mond-Ebene [rad]
+    pub start_inclination: f64,
+    /// Phasenwinkel auf der Startbahn, gemessen von der Erde-Mond-Achse [rad]
+    /// (Standard: Zündpunkt der TLI so, dass das Apogäum beim Mond liegt)
+    pub start_phase_angle: f64,
+    /// Echtzeit-Modus: Sim-Sekunden pro Wall-Clock-Sekunde (None = so schnell wie möglich)
+    pub realtime_scale: Option<f64>,
+    /// Maximale überlebbare Aufprallgeschwindigkeit bei Oberflächenkontakt [m/s]
+    pub survivable_impact_speed: f64,
+    /// Höhe, unterhalb der die Guidance ein Aufsetzen erkennt [m]
+    pub touchdown_altitude: f64,
+    /// Maximale Geschwindigkeit, bei der die Guidance ein Aufsetzen als Landung wertet [m/s]
+    pub touchdown_speed: f64,
+    /// Zielkörper (Name, Radius, μ, Abstand zur Erde)
+    pub target: TargetBody,
+    /// Physikalische Konstanten der Erde, g0 und Singularitätsbehandlung der
+    /// Gravitation für Startbahn, Körper und Integration
+    pub constants: PhysicsConstants,
+    /// Seed für Sensorrauschen (gleicher Seed = identischer Lauf)
+    pub seed: u64,
+    /// RK4-Teilschritte je Zeitschritt (Schub über den Zeitschritt konstant)
+    pub physics_substeps: u32,
+    /// Integrationsverfahren der Translationsdynamik (im mitrotierenden System stets RK4)
+    pub integrator: Integrator,
+    /// Störkräfte (J2, Sonne, Strahlungsdruck, Luftwiderstand; Standard: nur
+    /// Punktmassen-Gravitation, nicht im mitrotierenden System)
+    pub forces: ForceModel,
+    /// Zeitkonstante für Schubaufbau und -abfall [s] (0 = sprunghaft)
+    pub thrust_tau: f64,
+    /// Anfangslage und -drehrate (None = Einheitslage in Ruhe)
+    pub initial_attitude: Option<InitialAttitude>,
+    /// Lageregelung über Triebwerksschwenkung statt abstraktem Moment während Burns
+    pub gimbal_control: bool,
+    /// Lageregelung über RCS-Düsen statt abstraktem Moment; ihr Treibstoff
+    /// stammt aus dem Tank des Fahrzeugs und zählt zum Verbrauch
+    pub rcs_control: bool,
+    /// Abstand der Positionsmessungen [s] (0 = jeder Zeitschritt), Predict läuft je `dt`
+    pub sensor_interval: f64,
+    /// χ²-Schwelle für das NIS, oberhalb der Positionsmessungen verworfen werden
+    /// (None = kein Gating)
+    pub outlier_gate: Option<f64>,
+    /// Treibstoffreserve als Anteil des Fahrzeug-Treibstoffs (ohne Unterstufen),
+    /// die nur im Abbruchmodus verbrannt wird
+    pub propellant_reserve_fraction: f64,
+    /// Dynamik im mit dem Zielkörper umlaufenden System (Erde-Ziel-Achse fest,
+    /// Coriolis- und Zentrifugalkraft); Guidance rechnet weiter inertial
+    pub rotating_frame: bool,
+    /// Abwerfbare Unterstufen in Zündreihenfolge (leer = einstufig)
+    ///
+    /// Die Stufen tragen das durch `initial_mass`, `dry_mass`, `isp` und
+    /// `max_thrust` beschriebene Fahrzeug, das nach der letzten Trennung übrig bleibt.
+    pub stages: Vec<Stage>,
+    /// Geskriptete Burns ersetzen die Guidance
+    pub scripted_burns: Option<BurnSchedule>,
+    /// Tabellierte Position des Zielkörpers statt fester Position auf der x-Achse
+    /// (nicht mit `rotating_frame`)
+    pub moon_ephemeris: Option<MoonEphemeris>,
 }
 
 impl Default for SimConfig {
@@ -41,29 +165,324 @@ impl Default for SimConfig {
             initial_mass: 45_000.0,     // 45 Tonnen
             dry_mass: 5_000.0,          // 5 Tonnen
             telemetry_interval: 60.0,   // Alle 60 Sekunden
+            record_trajectory: false,   // Speicher sparen
+            lunar_frame_telemetry: true,
+            quiet: false,
+            start_altitude: 200_000.0,  // 200km LEO
+            start_inclination: 0.0,
+            start_phase_angle: 150f64.to_radians(), // TLI ab T+0 trifft den Mond
+            realtime_scale: None,
+            survivable_impact_speed: 5.0,
+            touchdown_altitude: 10.0,
+            touchdown_speed: SOFT_LANDING_SPEED,
+            target: TargetBody::moon(),
+            constants: PhysicsConstants::STANDARD,
+            seed: 42,
+            physics_substeps: 1,
+            integrator: Integrator::Rk4,
+            forces: ForceModel::default(),
+            thrust_tau: 0.0,
+            initial_attitude: None,
+            gimbal_control: false,
+            rcs_control: false,
+            sensor_interval: 0.0,
+            outlier_gate: None,
+            propellant_reserve_fraction: 0.0,
+            rotating_frame: false,
+            stages: Vec::new(),
+            scripted_burns: None,
+            moon_ephemeris: None,
+        }
+    }
+}
+
+/// Anfangslage des Fahrzeugs (z.B. Taumeln nach der Abtrennung)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct InitialAttitude {
+    /// Roll-, Nick- und Gierwinkel [rad]
+    pub euler: [f64; 3],
+    /// Winkelgeschwindigkeit [rad/s]
+    pub angular_velocity: [f64; 3],
+}
+
+impl InitialAttitude {
+    /// Lageregler im Anfangszustand (hält die Anfangslage)
+    pub fn controller(&self) -> AttitudeController {
+        let [roll, pitch, yaw] = self.euler;
+        AttitudeController::with_initial_state(
+            AttitudeController::from_euler(roll, pitch, yaw),
+            Vector3::from(self.angular_velocity),
+        )
+    }
+}
+
+/// Abwerfbare Stufe mit eigenem Triebwerk
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stage {
+    /// Strukturmasse, die bei der Trennung abgeworfen wird [kg]
+    pub dry_mass: f64,
+    /// Treibstoff der Stufe [kg]
+    pub propellant: f64,
+    /// Spezifischer Impuls [s]
+    pub isp: f64,
+    /// Maximaler Schub [N]
+    pub max_thrust: f64,
+}
+
+impl SimConfig {
+    /// Startmasse einschließlich aller Unterstufen [kg]
+    pub fn launch_mass(&self) -> f64 {
+        self.mass_above_stage(0)
+    }
+
+    /// Masse des Stapels ab Stufe `index` aufwärts [kg] (`index == stages.len()`: Fahrzeug)
+    fn mass_above_stage(&self, index: usize) -> f64 {
+        self.initial_mass
+            + self.stages[index.min(self.stages.len())..]
+                .iter()
+                .map(|stage| stage.dry_mass + stage.propellant)
+                .sum::<f64>()
+    }
+
+    /// Lädt ein Missionsprofil aus einer TOML-Datei
+    pub fn from_toml_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let text = std::fs::read_to_string(path).map_err(ConfigError::Io)?;
+        Self::from_toml_str(&text)
+    }
+
+    /// Parst ein Missionsprofil im TOML-Format
+    pub fn from_toml_str(text: &str) -> Result<Self, ConfigError> {
+        let config: Self = toml::from_str(text).map_err(ConfigError::Parse)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Prüft Kombinationen von Einstellungen, die die Simulation nicht umsetzt
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.rotating_frame && self.forces != ForceModel::default() {
+            return Err(ConfigError::Invalid(
+                "Störkräfte (forces) im mitrotierenden System nicht unterstützt",
+            ));
+        }
+        if self.rotating_frame && self.integrator != Integrator::Rk4 {
+            return Err(ConfigError::Invalid(
+                "Im mitrotierenden System ist nur der RK4-Integrator verfügbar",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Standard-Guidance für den konfigurierten Zielkörper (Ziel: dessen Oberfläche)
+    pub fn guidance_computer(&self) -> GuidanceComputer {
+        let target_pos = Vector3::new(self.target.distance_from_earth, 0.0, 0.0);
+        let surface = target_pos - Vector3::new(self.target.radius, 0.0, 0.0);
+        let mut guidance = GuidanceComputer::new(surface, self.max_thrust);
+        guidance.isp = self.isp;
+        guidance.target_radius = self.target.radius;
+        guidance.target_mu = self.target.mu;
+        guidance.target_body = 1; // Reihenfolge wie in `MoonMissionSim::bodies`
+        guidance.central_mu = self.constants.g * self.constants.m_earth;
+        guidance.central_radius = self.constants.r_earth;
+        guidance.touchdown_altitude = self.touchdown_altitude;
+        guidance.touchdown_speed = self.touchdown_speed;
+        guidance.terrain_height = self.target.terrain_height;
+        guidance
+    }
+
+    /// Serialisiert die Konfiguration als TOML-Missionsprofil
+    pub fn to_toml_string(&self) -> String {
+        toml::to_string(self).expect("SimConfig ist immer als TOML darstellbar")
+    }
+}
+
+/// Fehler beim Laden eines Missionsprofils
+#[derive(Debug)]
+pub enum ConfigError {
+    /// Datei nicht lesbar
+    Io(std::io::Error),
+    /// Ungültiges TOML oder falscher Feldtyp
+    Parse(toml::de::Error),
+    /// Unzulässige Kombination von Einstellungen
+    Invalid(&'static str),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(err) => write!(f, "Missionsprofil nicht lesbar: {}", err),
+            ConfigError::Parse(err) => write!(f, "Ungültiges Missionsprofil: {}", err),
+            ConfigError::Invalid(reason) => write!(f, "Ungültiges Missionsprofil: {}", reason),
         }
     }
 }
 
+impl std::error::Error for ConfigError {}
+
+/// Ergebnis eines einzelnen Simulationsschritts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StepOutcome {
+    /// Mission läuft weiter
+    Running,
+    /// Erfolgreich gelandet
+    Landed,
+    /// Abbruch durch FDIR (System kritisch)
+    SystemFailure,
+    /// Treibstoff erschöpft
+    OutOfFuel,
+    /// Aufprall auf eine Oberfläche oberhalb der überlebbaren Geschwindigkeit
+    Crashed,
+    /// Ungewollte Fluchtbahn aus dem Erdsystem (Ziel nicht erreichbar)
+    Escaped,
+    /// Maximale Simulationszeit erreicht
+    TimeLimit,
+    /// NaN/∞ im Zustand (numerischer Fehler), Lauf abgebrochen
+    NonFiniteState,
+}
+
+/// Zusammenfassung eines Triebwerksbrennvorgangs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BurnReport {
+    /// Missionsphase, der der Burn zugeordnet ist
+    pub phase: MissionPhase,
+    /// Zündzeitpunkt [s]
+    pub start_time: f64,
+    /// Brennschluss [s]
+    pub end_time: f64,
+    /// Erzielte Geschwindigkeitsänderung [m/s]
+    pub delta_v: f64,
+    /// Verbrauchter Treibstoff [kg]
+    pub propellant: f64,
+}
+
 /// Simulationsergebnis
 pub struct SimResult {
     pub success: bool,
+    /// Grund für das Missionsende
+    pub outcome: StepOutcome,
+    /// Bewertung des Aufsetzens (None wenn kein Oberflächenkontakt mit dem Mond)
+    pub landing: Option<LandingOutcome>,
     pub final_state: SpacecraftState,
     pub mission_time: f64,
     pub fuel_used: f64,
     pub telemetry: TelemetryLogger,
+    /// Aufgezeichnete Zustände (leer wenn `record_trajectory` deaktiviert)
+    pub trajectory: Vec<SpacecraftState>,
+    /// Missionsphase zu jedem Zustand in `trajectory`
+    pub trajectory_phases: Vec<MissionPhase>,
+    /// Meilensteine der Mission
+    pub timeline: Timeline,
+    /// Abgeschlossene Burns in zeitlicher Reihenfolge
+    burn_reports: Vec<BurnReport>,
+}
+
+impl SimResult {
+    /// Δv und Treibstoffverbrauch je Burn
+    pub fn burn_reports(&self) -> &[BurnReport] {
+        &self.burn_reports
+    }
+
+    /// Schreibt die aufgezeichnete Trajektorie als NDJSON (ein JSON-Objekt je Zeile
+    /// mit `time`, `position`, `velocity`, `mass`, `phase`), z.B. zum Plotten
+    ///
+    /// Benötigt `record_trajectory`, sonst entsteht eine leere Datei.
+    pub fn export_trajectory_ndjson<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        for (state, phase) in self.trajectory.iter().zip(&self.trajectory_phases) {
+            let line = serde_json::json!({
+                "time": state.time,
+                "position": [state.position.x, state.position.y, state.position.z],
+                "velocity": [state.velocity.x, state.velocity.y, state.velocity.z],
+                "mass": state.mass,
+                "phase": format!("{:?}", phase),
+            });
+            writeln!(writer, "{}", line)?;
+        }
+        writer.flush()
+    }
+}
+
+/// Checkpoint des vollständigen Simulationszustands
+#[derive(Debug, Clone)]
+pub struct SimSnapshot {
+    pub state: SpacecraftState,
+    pub guidance: Box<dyn Guidance>,
+    pub kalman: KalmanFilter,
+    pub attitude: AttitudeController,
+    pub fdir: FDIRManager,
+    pub moon_pos: Vector3<f64>,
+    pub landing: Option<LandingOutcome>,
+    pub timeline: Timeline,
+    rng: StdRng,
+    iteration: u64,
+    last_telemetry: f64,
+    last_measurement: Option<f64>,
+    stage_index: usize,
+    jettisoned_mass: f64,
+    reserve_reached: bool,
+    actual_thrust: Vector3<f64>,
+    current_burn: Option<BurnReport>,
+    burn_reports: Vec<BurnReport>,
 }
 
+/// Callback bei Phasenwechsel: (alte Phase, neue Phase, Zustand)
+pub type PhaseChangeCallback = Box<dyn FnMut(MissionPhase, MissionPhase, &SpacecraftState) + Send>;
+
 /// Hauptsimulation
 pub struct MoonMissionSim {
     pub config: SimConfig,
     pub state: SpacecraftState,
     pub earth_pos: Vector3<f64>,
     pub moon_pos: Vector3<f64>,
-    pub guidance: GuidanceComputer,
+    pub guidance: Box<dyn Guidance>,
     pub kalman: KalmanFilter,
+    pub attitude: AttitudeController,
+    /// Schwenkbares Haupttriebwerk (aktiv mit `gimbal_control`)
+    pub gimbal: EngineGimbal,
+    /// Lageregelungsdüsen (aktiv mit `rcs_control`)
+    pub rcs: RcsCluster,
+    /// Mitrotierendes Bezugssystem (nur mit `rotating_frame`)
+    pub frame: Option<RotatingFrame>,
+    /// Ausrichtungsfehler der Schubachse im letzten Schritt [rad] (0 ohne Schub)
+    pub pointing_error: f64,
+    /// Tatsächlicher Schub nach Zünd-/Abschalttransiente [N] (siehe `thrust_tau`)
+    pub actual_thrust: Vector3<f64>,
+    /// Aufgeschalteter Fehler je IMU-Kanal [m] (Fehlerinjektion, nominal 0)
+    pub imu_channel_error: [Vector3<f64>; 3],
+    /// Aufgeschalteter Fehler je Flugrechner-Kanal [N] (Fehlerinjektion, nominal 0)
+    pub flight_computer_error: [Vector3<f64>; 3],
     pub fdir: FDIRManager,
     pub telemetry: TelemetryLogger,
+    pub trajectory: Vec<SpacecraftState>,
+    /// Missionsphase zu jedem aufgezeichneten Zustand
+    pub trajectory_phases: Vec<MissionPhase>,
+    /// Ziel für Status- und Phasenmeldungen
+    pub logger: Box<dyn MissionLogger>,
+    /// Optionaler Callback bei Phasenwechsel der Guidance
+    pub on_phase_change: Option<PhaseChangeCallback>,
+    /// Bewertung des Aufsetzens auf dem Mond
+    pub landing: Option<LandingOutcome>,
+    /// Meilensteine der Mission (Phasen, Burns, Touchdown)
+    pub timeline: Timeline,
+    /// Zufallsquelle für Sensorrauschen
+    rng: StdRng,
+    /// Anzahl ausgeführter Schritte
+    iteration: u64,
+    /// Zeitpunkt der letzten Telemetrie [s]
+    last_telemetry: f64,
+    /// Zeitpunkt der letzten Positionsmessung [s]
+    last_measurement: Option<f64>,
+    /// Index der aktiven Stufe in `config.stages` (== Anzahl: nur noch Fahrzeug)
+    stage_index: usize,
+    /// Bei Stufentrennungen abgeworfene Masse [kg]
+    jettisoned_mass: f64,
+    /// Treibstoffreserve erreicht (Burns außer im Abbruch gesperrt)
+    reserve_reached: bool,
+    /// Letzter Zustand, der die Endlichkeitsprüfung bestanden hat
+    last_good_state: SpacecraftState,
+    /// Laufender Burn (None bei abgeschaltetem Triebwerk)
+    current_burn: Option<BurnReport>,
+    /// Abgeschlossene Burns
+    burn_reports: Vec<BurnReport>,
 }
 
 impl MoonMissionSim {
@@ -71,18 +490,28 @@ impl MoonMissionSim {
         // Erde im Ursprung
         let earth_pos = Vector3::zeros();
 
-        // Mond auf X-Achse
-        let moon_pos = Vector3::new(EARTH_MOON_DISTANCE, 0.0, 0.0);
+        // Zielkörper (Mond) auf X-Achse oder aus der Ephemeride
+        let moon_pos = match &config.moon_ephemeris {
+            Some(ephemeris) => ephemeris.position_at(0.0),
+            None => Vector3::new(config.target.distance_from_earth, 0.0, 0.0),
+        };
 
         // Raumschiff startet bereits im LEO (realistisch nach Raketenstart)
-        let initial_pos = Vector3::new(6.571e6, 0.0, 0.0);  // 200km Orbit
-        let initial_vel = Vector3::new(0.0, 7_784.0, 0.0);  // Orbitalgeschwindigkeit
+        let (initial_pos, initial_vel) = Self::initial_orbit(&config);
 
-        let state = SpacecraftState::new(initial_pos, initial_vel, config.initial_mass);
+        // Mitrotierendes System: Startgeschwindigkeit relativ zur ruhenden Erde umrechnen
+        let frame = config.rotating_frame.then(|| {
+            let earth_mu = config.constants.earth(earth_pos).mu;
+            RotatingFrame::two_body(earth_mu, config.target.mu, config.target.distance_from_earth)
+        });
+        let initial_vel = match &frame {
+            Some(frame) => frame.to_rotating_velocity(&initial_pos, &initial_vel, &earth_pos),
+            None => initial_vel,
+        };
+
+        let state = SpacecraftState::new(initial_pos, initial_vel, config.launch_mass());
 
-        // Ziel: Mondoberfläche
-        let moon_surface = moon_pos - Vector3::new(1.737e6, 0.0, 0.0);
-        let guidance = GuidanceComputer::new(moon_surface, config.max_thrust);
+        let guidance: Box<dyn Guidance> = Box::new(config.guidance_computer());
 
         // Kalman-Filter initialisieren
         let kalman_state = Vector6::new(
@@ -93,145 +522,949 @@ impl MoonMissionSim {
             initial_vel.y,
             initial_vel.z,
         );
-        let kalman = KalmanFilter::new(kalman_state);
+        // Messrauschen passend zum injizierten Rauschen:
+        // Gleichverteilung der Breite s hat Varianz σ² = s² / 12
+        let mut kalman = KalmanFilter::new(kalman_state)
+            .with_process_noise(Self::kalman_process_noise())
+            .with_measurement_noise(Matrix3::identity() * SENSOR_NOISE.powi(2) / 12.0);
+        kalman.outlier_gate = config.outlier_gate;
 
-        let fdir = FDIRManager::new();
-        let telemetry = TelemetryLogger::new();
+        // Watchdog auf Missionszeit: Timeout muss mindestens zwei Zeitschritte abdecken
+        let mut fdir = FDIRManager::new();
+        fdir.set_mission_time(state.time);
+        let min_timeout = Duration::from_secs_f64(2.0 * config.dt);
+        fdir.watchdog.timeout = fdir.watchdog.timeout.max(min_timeout);
+        // Unabhängig verrauschte IMU-Kanäle liegen höchstens SENSOR_NOISE auseinander
+        for (axis, name) in IMU_AXES.iter().enumerate() {
+            let mut imu = RedundantSubsystem::new(name).with_tolerance(SENSOR_NOISE);
+            for channel in 0..3 {
+                imu.set_channel(channel, state.position[axis]);
+            }
+            fdir.register_subsystem(imu);
+        }
+        for name in FLIGHT_COMPUTER_AXES {
+            let mut computer =
+                RedundantSubsystem::new(name).with_tolerance(FLIGHT_COMPUTER_TOLERANCE);
+            for channel in 0..3 {
+                computer.set_channel(channel, 0.0);
+            }
+            fdir.register_subsystem(computer);
+        }
+        let mut telemetry = TelemetryLogger::new();
+        telemetry.set_mission_time(state.time);
+        let rng = StdRng::seed_from_u64(config.seed);
+        let mut attitude = config
+            .initial_attitude
+            .map_or_else(AttitudeController::new, |initial| initial.controller());
+        attitude.kp = ATTITUDE_KP;
+        attitude.kd = ATTITUDE_KD;
+        let logger: Box<dyn MissionLogger> = if config.quiet {
+            Box::new(NullLogger)
+        } else {
+            Box::new(StdoutLogger)
+        };
 
         Self {
             config,
-            state,
+            state: state.clone(),
             earth_pos,
             moon_pos,
             guidance,
             kalman,
+            attitude,
+            gimbal: EngineGimbal::new(physics::VEHICLE_LENGTH / 2.0, GIMBAL_MAX_ANGLE),
+            rcs: RcsCluster::standard(physics::VEHICLE_RADIUS, RCS_JET_THRUST, 0.0),
+            frame,
+            pointing_error: 0.0,
+            actual_thrust: Vector3::zeros(),
+            imu_channel_error: [Vector3::zeros(); 3],
+            flight_computer_error: [Vector3::zeros(); 3],
             fdir,
             telemetry,
+            trajectory: Vec::new(),
+            trajectory_phases: Vec::new(),
+            logger,
+            on_phase_change: None,
+            landing: None,
+            timeline: Timeline::new(),
+            rng,
+            iteration: 0,
+            last_telemetry: 0.0,
+            last_measurement: None,
+            stage_index: 0,
+            jettisoned_mass: 0.0,
+            reserve_reached: false,
+            last_good_state: state,
+            current_burn: None,
+            burn_reports: Vec::new(),
+        }
+    }
+
+    /// Startzustand auf kreisförmiger Erdumlaufbahn
+    /// v = sqrt(G * M / r), Bahnebene um die X-Achse (Knotenlinie) geneigt
+    fn initial_orbit(config: &SimConfig) -> (Vector3<f64>, Vector3<f64>) {
+        let earth = config.constants.earth(Vector3::zeros());
+        let radius = earth.radius + config.start_altitude;
+        let speed = (earth.mu / radius).sqrt();
+        let (sin_phase, cos_phase) = config.start_phase_angle.sin_cos();
+
+        let tilt = Rotation3::from_axis_angle(&Vector3::x_axis(), config.start_inclination);
+        let position = tilt * Vector3::new(cos_phase, sin_phase, 0.0) * radius;
+        let velocity = tilt * Vector3::new(-sin_phase, cos_phase, 0.0) * speed;
+        (position, velocity)
+    }
+
+    /// Prozessrauschen für das Modell konstanter Geschwindigkeit
+    ///
+    /// Unmodellierte Beschleunigung (Erdgravitation im LEO, Schub) bis etwa
+    /// UNMODELED_ACCEL; Varianz pro Sekunde für Position (a/2)² und Geschwindigkeit a².
+    fn kalman_process_noise() -> Matrix6<f64> {
+        let a2 = UNMODELED_ACCEL.powi(2);
+        Matrix6::from_diagonal(&Vector6::new(a2 / 4.0, a2 / 4.0, a2 / 4.0, a2, a2, a2))
+    }
+
+    /// Trägheitstensor für die gegebene Fahrzeugmasse [kg·m²]
+    pub fn inertia_for_mass(&self, mass: f64) -> Matrix3<f64> {
+        physics::vehicle_inertia(self.config.dry_mass, mass)
+    }
+
+    /// Lagedynamik über einen Zeitschritt (feinere Unterschritte für Stabilität)
+    ///
+    /// Das Reglermoment ist ein physikalisches Moment, begrenzt auf
+    /// ATTITUDE_MAX_TORQUE bzw. die Triebwerksschwenkung; ein schwereres
+    /// Fahrzeug dreht daher langsamer.
+    fn update_attitude(&mut self, thrust: &Vector3<f64>) -> Vector3<f64> {
+        let thrust_magnitude = thrust.norm();
+        if thrust_magnitude > 0.0 {
+            self.attitude.slew_towards(thrust, ATTITUDE_SLEW_RATE);
+        }
+        let gimbaled = self.config.gimbal_control && thrust_magnitude > 0.0;
+
+        let inertia = self.inertia_for_mass(self.state.mass);
+        let substeps = (self.config.dt / ATTITUDE_DT).ceil().max(1.0) as usize;
+        let dt = self.config.dt / substeps as f64;
+        for _ in 0..substeps {
+            let commanded = self.attitude.compute_torque();
+            let torque = if gimbaled {
+                // Moment nur so weit, wie die Triebwerksschwenkung es erzeugen kann
+                let rotation = self.attitude.orientation.to_rotation_matrix();
+                self.gimbal.command_torque(&(rotation.transpose() * commanded), thrust_magnitude);
+                rotation * self.gimbal.force_and_torque(thrust_magnitude).1
+            } else if self.config.rcs_control {
+                self.fire_rcs(&commanded, dt)
+            } else if commanded.norm() > ATTITUDE_MAX_TORQUE {
+                commanded * (ATTITUDE_MAX_TORQUE / commanded.norm())
+            } else {
+                commanded
+            };
+            self.attitude.update_with_inertia(&torque, &inertia, dt);
+        }
+
+        if gimbaled {
+            // Schub wirkt entlang der geschwenkten Triebwerksachse
+            self.attitude.orientation * self.gimbal.force_and_torque(thrust_magnitude).0
+        } else {
+            *thrust
+        }
+    }
+
+    /// RCS-Moment für das Kommando `commanded` (Inertialsystem) über `dt`
+    ///
+    /// Die Düsen zehren vom Tank der aktiven Stufe bis zum Brennschluss; der
+    /// verbrauchte Treibstoff wird von der Fahrzeugmasse abgezogen.
+    fn fire_rcs(&mut self, commanded: &Vector3<f64>, dt: f64) -> Vector3<f64> {
+        let rotation = self.attitude.orientation.to_rotation_matrix();
+        let available = (self.state.mass - self.burnout_mass()).max(0.0);
+        self.rcs.propellant = available;
+        let torque = self.rcs.fire_pulse_width(&(rotation.transpose() * commanded), dt);
+        self.state.mass -= available - self.rcs.propellant;
+        rotation * torque
+    }
+
+    /// Zünd- und Abschalttransiente: Tiefpass 1. Ordnung vom kommandierten zum
+    /// tatsächlichen Schub
+    ///
+    /// Für ein über `dt` konstantes Kommando c wirkt der Mittelwert der
+    /// Filterantwort c + (F₀ - c)·(1 - e^(-dt/τ))·τ/dt über den Schritt, so dass
+    /// Impuls und Treibstoffverbrauch nicht von `dt` abhängen; `actual_thrust`
+    /// hält den Filterwert am Schrittende. Unter 0,1 % des Maximalschubs gilt das
+    /// Triebwerk als aus.
+    fn thrust_transient(&mut self, commanded: &Vector3<f64>) -> Vector3<f64> {
+        let (tau, dt) = (self.config.thrust_tau, self.config.dt);
+        if tau <= 0.0 {
+            self.actual_thrust = *commanded;
+            return self.actual_thrust;
+        }
+        let decay = (-dt / tau).exp();
+        let offset = self.actual_thrust - commanded;
+        let filtered = commanded + offset * decay;
+        self.actual_thrust = if filtered.norm() < 1e-3 * self.config.max_thrust {
+            Vector3::zeros()
+        } else {
+            filtered
+        };
+        commanded + offset * ((1.0 - decay) * tau / dt)
+    }
+
+    /// Winkel zwischen kommandierter Schubrichtung und Körper-Schubachse (+z) [rad]
+    pub fn thrust_pointing_error(&self, commanded: &Vector3<f64>) -> f64 {
+        if commanded.norm() < 1e-9 {
+            return 0.0;
+        }
+        let axis = self.attitude.orientation * Vector3::z();
+        axis.angle(commanded)
+    }
+
+    /// Verbleibendes Δv aller noch vorhandenen Stufen und des Fahrzeugs [m/s]
+    pub fn remaining_delta_v(&self) -> f64 {
+        // Stufe für Stufe: Brennschluss, dann Trennung auf die Masse des Reststapels
+        let mut stack = self.state.clone();
+        let mut delta_v = 0.0;
+        for (index, stage) in self.config.stages.iter().enumerate().skip(self.stage_index) {
+            let upper = self.config.mass_above_stage(index + 1);
+            delta_v += stack.remaining_delta_v(upper + stage.dry_mass, stage.isp);
+            stack.mass = upper;
+        }
+        delta_v + stack.remaining_delta_v(self.config.dry_mass, self.config.isp)
+    }
+
+    /// Aktive Stufe (None: alle Unterstufen abgetrennt)
+    pub fn active_stage(&self) -> Option<&Stage> {
+        self.config.stages.get(self.stage_index)
+    }
+
+    /// Masse bei Brennschluss der aktiven Stufe [kg]
+    fn burnout_mass(&self) -> f64 {
+        match self.active_stage() {
+            Some(stage) => self.config.mass_above_stage(self.stage_index + 1) + stage.dry_mass,
+            None => self.config.dry_mass,
+        }
+    }
+
+    /// Untergrenze der Masse für Burns [kg]: Brennschluss zuzüglich der
+    /// Treibstoffreserve des Fahrzeugs, im Abbruchmodus nur der Brennschluss
+    fn usable_mass_floor(&self) -> f64 {
+        if self.active_stage().is_some() || self.guidance.phase() == MissionPhase::Abort {
+            return self.burnout_mass();
+        }
+        let propellant = self.config.initial_mass - self.config.dry_mass;
+        self.config.dry_mass + self.config.propellant_reserve_fraction * propellant
+    }
+
+    /// Meldet einmalig das Erreichen der Treibstoffreserve
+    fn note_reserve_reached(&mut self) {
+        if self.reserve_reached {
+            return;
+        }
+        self.reserve_reached = true;
+        self.logger.log("⛽ Propellant reserve reached - burns inhibited except abort");
+        self.telemetry.log_event(
+            SubsystemId::Propulsion,
+            2011,
+            EventSeverity::Warning,
+            "Propellant reserve reached",
+        );
+    }
+
+    /// Spezifischer Impuls des aktiven Triebwerks [s]
+    fn engine_isp(&self) -> f64 {
+        self.active_stage().map_or(self.config.isp, |stage| stage.isp)
+    }
+
+    /// Maximaler Schub des aktiven Triebwerks [N]
+    fn engine_max_thrust(&self) -> f64 {
+        self.active_stage().map_or(self.config.max_thrust, |stage| stage.max_thrust)
+    }
+
+    /// Verbleibender Anteil am gesamten Treibstoff aller Stufen [%]
+    fn fuel_percent(&self) -> f64 {
+        let stages = &self.config.stages;
+        let total = self.config.launch_mass() - self.config.dry_mass
+            - stages.iter().map(|stage| stage.dry_mass).sum::<f64>();
+        let remaining = self.state.mass - self.config.dry_mass
+            - stages[self.stage_index..].iter().map(|stage| stage.dry_mass).sum::<f64>();
+        remaining / total * 100.0
+    }
+
+    /// Trennt die aktive Stufe ab: Strukturmasse und Resttreibstoff werden abgeworfen,
+    /// danach gelten Isp und Schub der nächsten Stufe (bzw. des Fahrzeugs)
+    ///
+    /// Wird bei Brennschluss automatisch ausgelöst, kann aber auch zeitgesteuert
+    /// aufgerufen werden. Gibt `false` zurück, wenn keine Stufe mehr vorhanden ist.
+    pub fn stage(&mut self) -> bool {
+        if self.active_stage().is_none() {
+            return false;
+        }
+        let remaining = self.config.mass_above_stage(self.stage_index + 1);
+        let dropped = self.state.mass - remaining;
+        self.jettisoned_mass += dropped;
+        self.state.mass = remaining;
+        self.stage_index += 1;
+
+        let description = format!("Stage {} separated ({:.0} kg)", self.stage_index, dropped);
+        self.logger.log(&format!("🔻 {}", description));
+        self.timeline.record(self.state.time, MilestoneKind::StageSeparation, &description);
+        self.telemetry.log_event(
+            SubsystemId::Propulsion,
+            2010,
+            EventSeverity::Info,
+            "Stage separation",
+        );
+        true
+    }
+
+    /// Gravitierende Körper im aktuellen Simulationszustand
+    pub fn bodies(&self) -> [GravBody; 2] {
+        [
+            self.config.constants.earth(self.earth_pos),
+            self.config.target.grav_body(self.moon_pos),
+        ]
+    }
+
+    /// Ersetzt das Ziel für Status- und Phasenmeldungen
+    pub fn with_logger(mut self, logger: Box<dyn MissionLogger>) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    /// Ersetzt die Standard-Guidance durch eine eigene Strategie
+    pub fn with_guidance(mut self, guidance: Box<dyn Guidance>) -> Self {
+        self.guidance = guidance;
+        self
+    }
+
+    /// Registriert Callback für Phasenwechsel
+    pub fn with_phase_callback(mut self, callback: PhaseChangeCallback) -> Self {
+        self.on_phase_change = Some(callback);
+        self
+    }
+
+    /// Ist eine neue Positionsmessung fällig? (Toleranz gegen Rundung der Sim-Zeit)
+    fn measurement_due(&self) -> bool {
+        self.last_measurement
+            .is_none_or(|t| self.state.time - t >= self.config.sensor_interval - 1e-9)
+    }
+
+    /// Erstellt Checkpoint zum Fortsetzen oder Verzweigen eines Laufs
+    pub fn snapshot(&self) -> SimSnapshot {
+        SimSnapshot {
+            state: self.state.clone(),
+            guidance: self.guidance.clone(),
+            kalman: self.kalman.clone(),
+            attitude: self.attitude.clone(),
+            fdir: self.fdir.clone(),
+            moon_pos: self.moon_pos,
+            landing: self.landing,
+            timeline: self.timeline.clone(),
+            rng: self.rng.clone(),
+            iteration: self.iteration,
+            last_telemetry: self.last_telemetry,
+            last_measurement: self.last_measurement,
+            stage_index: self.stage_index,
+            jettisoned_mass: self.jettisoned_mass,
+            reserve_reached: self.reserve_reached,
+            actual_thrust: self.actual_thrust,
+            current_burn: self.current_burn,
+            burn_reports: self.burn_reports.clone(),
+        }
+    }
+
+    /// Stellt einen Checkpoint wieder her
+    pub fn restore(&mut self, snapshot: SimSnapshot) {
+        self.state = snapshot.state;
+        self.guidance = snapshot.guidance;
+        self.kalman = snapshot.kalman;
+        self.attitude = snapshot.attitude;
+        self.fdir = snapshot.fdir;
+        self.moon_pos = snapshot.moon_pos;
+        self.landing = snapshot.landing;
+        self.timeline = snapshot.timeline;
+        self.rng = snapshot.rng;
+        self.iteration = snapshot.iteration;
+        self.last_telemetry = snapshot.last_telemetry;
+        self.last_measurement = snapshot.last_measurement;
+        self.stage_index = snapshot.stage_index;
+        self.jettisoned_mass = snapshot.jettisoned_mass;
+        self.reserve_reached = snapshot.reserve_reached;
+        self.actual_thrust = snapshot.actual_thrust;
+        self.current_burn = snapshot.current_burn;
+        self.burn_reports = snapshot.burn_reports;
+        self.last_good_state = self.state.clone();
+    }
+
+    /// Closed-Loop-Schub der Guidance inkl. Phasenwechsel-Erkennung
+    fn guidance_thrust(&mut self) -> Vector3<f64> {
+        // Planung mit dem Triebwerk der aktiven Stufe
+        self.guidance.set_engine(self.engine_max_thrust(), self.engine_isp());
+        let phase_before = self.guidance.phase();
+        let bodies = self.bodies();
+        let inertial = self.inertial_state();
+        let thrust = self.guidance.compute_thrust(&inertial, &bodies, self.logger.as_mut());
+        if self.guidance.phase() != phase_before {
+            self.timeline.record(
+                self.state.time,
+                MilestoneKind::PhaseChange,
+                &format!("{:?} → {:?}", phase_before, self.guidance.phase()),
+            );
+            if self.guidance.phase() == MissionPhase::Landed {
+                self.landing = Some(LandingOutcome::classify(
+                    inertial.speed(),
+                    self.config.survivable_impact_speed,
+                ));
+                self.timeline.record(
+                    self.state.time,
+                    MilestoneKind::Touchdown,
+                    &format!("Touchdown at {:.1} m/s", inertial.speed()),
+                );
+            }
+            if let Some(callback) = self.on_phase_change.as_mut() {
+                callback(phase_before, self.guidance.phase(), &inertial);
+            }
+        }
+        thrust
+    }
+
+    /// Meldet einen Fehler an FDIR (z.B. zum Testen von Abbruchpfaden)
+    pub fn inject_fault(&mut self, reason: &str) {
+        self.fdir.handle_fault(reason, self.logger.as_mut());
+        self.forward_fdir_events();
+        self.on_fault();
+    }
+
+    /// Reaktion auf einen erkannten Fehler: während Aufstieg oder Abstieg
+    /// auf die Abbruch-Parkbahn wechseln
+    fn on_fault(&mut self) {
+        if matches!(self.guidance.phase(), MissionPhase::Ascent | MissionPhase::Descent) {
+            let phase_before = self.guidance.phase();
+            if self.guidance.abort(self.logger.as_mut()) {
+                self.timeline.record(
+                    self.state.time,
+                    MilestoneKind::Abort,
+                    &format!("Abort during {:?}", phase_before),
+                );
+                self.telemetry.log_event(
+                    SubsystemId::GNC,
+                    2003,
+                    EventSeverity::Warning,
+                    "Abort to parking orbit",
+                );
+                if let Some(callback) = self.on_phase_change.as_mut() {
+                    callback(phase_before, MissionPhase::Abort, &self.state);
+                }
+            }
+        }
+    }
+
+    /// Prüft auf ungewollte Fluchtbahn: positive Erd-Bahnenergie, fliegt fort
+    /// und hat Bahn und Einflusssphäre des Zielkörpers bereits hinter sich
+    pub fn is_escaping(&self) -> bool {
+        let [earth, target] = self.bodies();
+        // Einflusssphäre nach Laplace: r = a * (μ_Ziel / μ_Erde)^(2/5)
+        let distance = self.config.target.distance_from_earth;
+        let soi = distance * (target.mu / earth.mu).powf(0.4);
+        // Bahnenergie nur inertial aussagekräftig
+        physics::is_escape_trajectory(&self.inertial_state(), &earth, distance + soi)
+    }
+
+    /// Zustand mit Geschwindigkeit relativ zur ruhenden Erde (im mitrotierenden
+    /// System v = v_rot + ω × (r - r_Erde), sonst unverändert); Achsen und
+    /// Körperpositionen bleiben die des Simulationssystems
+    fn inertial_state(&self) -> SpacecraftState {
+        let mut inertial = self.state.clone();
+        if let Some(frame) = &self.frame {
+            inertial.velocity += frame.omega.cross(&(inertial.position - self.earth_pos));
+        }
+        inertial
+    }
+
+    /// Menschenlesbares Missionsprotokoll (Meilensteine mit Missionszeit)
+    pub fn export_timeline(&self) -> String {
+        self.timeline.export()
+    }
+
+    /// Übernimmt FDIR-Ereignisse in die Telemetrie
+    fn forward_fdir_events(&mut self) {
+        for event in self.fdir.take_events() {
+            self.telemetry
+                .log_event(SubsystemId::FDIR, event.code, event.severity, &event.message);
         }
     }
 
+    /// Zustände nach jedem Schritt, bis die Mission endet (z.B. für Live-Plots)
+    ///
+    /// Der Schritt, der die Mission beendet (Landung, Aufprall, ...), liefert
+    /// seinen Zustand noch; das Zeitlimit beendet ohne weiteren Zustand.
+    pub fn states(&mut self) -> impl Iterator<Item = SpacecraftState> + '_ {
+        let mut finished = false;
+        std::iter::from_fn(move || {
+            if finished {
+                return None;
+            }
+            let time_before = self.state.time;
+            let outcome = self.step();
+            finished = outcome != StepOutcome::Running;
+            (!finished || self.state.time > time_before).then(|| self.state.clone())
+        })
+    }
+
     /// Führt die komplette Mission durch
     pub fn run(&mut self) -> SimResult {
-        println!("🚀 AZB_Raumschiff Mission Start!");
-        println!("   Ziel: Mondlandung");
-        println!("   Startmasse: {:.0} kg", self.config.initial_mass);
-        println!("   Max. Schub: {:.0} kN", self.config.max_thrust / 1000.0);
-        println!();
-
-        let initial_mass = self.config.initial_mass;
-        let mut last_telemetry = 0.0;
-        let mut iteration = 0;
-
-        while self.state.time < self.config.max_time {
-            // FDIR-Zyklus
-            self.fdir.run_cycle();
-            if !self.fdir.is_operational() {
-                println!("❌ Mission aborted: System critical failure");
-                break;
+        self.logger.log("🚀 AZB_Raumschiff Mission Start!");
+        self.logger.log("   Ziel: Mondlandung");
+        self.logger.log(&format!("   Startmasse: {:.0} kg", self.config.launch_mass()));
+        self.logger.log(&format!(
+            "   Max. Schub: {:.0} kN",
+            self.config.max_thrust / 1000.0
+        ));
+        self.logger.log("");
+
+        // Selbsttest vor Missionsbeginn
+        if !self.fdir.power_on_self_test() {
+            self.logger.log("⚠️ FDIR: Power-on self-test failed");
+        }
+        self.forward_fdir_events();
+        self.timeline.record(
+            self.state.time,
+            MilestoneKind::Liftoff,
+            &format!("Mission start in {:?}", self.guidance.phase()),
+        );
+
+        let wall_start = Instant::now();
+        let sim_start = self.state.time;
+
+        let outcome = loop {
+            let outcome = self.step();
+            if outcome != StepOutcome::Running {
+                break outcome;
             }
 
-            // Schub berechnen (Guidance)
-            let thrust = self.guidance.compute_thrust(
-                &self.state.position,
-                &self.state.velocity,
-                &self.moon_pos,
-            );
+            // Echtzeit-Pacing: auf Soll-Wall-Clock-Zeit warten (driftfrei)
+            if let Some(scale) = self.config.realtime_scale {
+                let sim_elapsed = self.state.time - sim_start;
+                if let Some(remaining) = pacing_delay(sim_elapsed, scale, wall_start.elapsed()) {
+                    std::thread::sleep(remaining);
+                }
+            }
+        };
+        self.timeline
+            .record(self.state.time, MilestoneKind::MissionEnd, &format!("{:?}", outcome));
+        self.burn_reports.extend(self.current_burn.take());
+
+        SimResult {
+            success: outcome == StepOutcome::Landed,
+            outcome,
+            landing: self.landing,
+            final_state: self.state.clone(),
+            mission_time: self.state.time,
+            fuel_used: self.config.launch_mass() - self.state.mass - self.jettisoned_mass,
+            telemetry: std::mem::take(&mut self.telemetry),
+            trajectory: std::mem::take(&mut self.trajectory),
+            trajectory_phases: std::mem::take(&mut self.trajectory_phases),
+            timeline: self.timeline.clone(),
+            burn_reports: self.burn_reports.clone(),
+        }
+    }
+
+    /// Burn-Buchführung nach der Integration: `delta_v` ist das Integral der
+    /// tatsächlich wirkenden Schubbeschleunigung über den Schritt;
+    /// Phasenwechsel beginnt einen neuen Burn
+    fn account_burn(&mut self, start_time: f64, start_mass: f64, delta_v: f64) {
+        let propellant = start_mass - self.state.mass;
+        let phase = self.guidance.phase();
+        if propellant <= 0.0 || self.current_burn.is_some_and(|burn| burn.phase != phase) {
+            self.burn_reports.extend(self.current_burn.take());
+        }
+        if propellant <= 0.0 {
+            return;
+        }
+        let burn = self.current_burn.get_or_insert(BurnReport {
+            phase,
+            start_time,
+            end_time: start_time,
+            delta_v: 0.0,
+            propellant: 0.0,
+        });
+        burn.end_time = self.state.time;
+        burn.delta_v += delta_v;
+        burn.propellant += propellant;
+    }
+
+    /// TMR-Navigation: jeder IMU-Kanal misst die Position mit eigenem Rauschen
+    /// (plus ggf. injiziertem Kanalfehler), die FDIR stimmt je Achse ab
+    fn vote_imu(&mut self) -> Vector3<f64> {
+        let mut channels = [Vector3::zeros(); 3];
+        for (channel, error) in channels.iter_mut().zip(self.imu_channel_error) {
+            *channel = add_sensor_noise(&self.state.position, SENSOR_NOISE, &mut self.rng) + error;
+        }
+        self.vote_axes(&IMU_AXES, channels)
+    }
+
+    /// TMR-Flugrechner: drei Rechner liefern das Schubkommando (plus ggf.
+    /// injizierten Kanalfehler), die FDIR stimmt je Achse ab
+    fn vote_flight_computer(&mut self, commanded: &Vector3<f64>) -> Vector3<f64> {
+        let channels = self.flight_computer_error.map(|error| commanded + error);
+        self.vote_axes(&FLIGHT_COMPUTER_AXES, channels)
+    }
+
+    /// Stimmt drei Vektor-Kanäle achsweise über die FDIR-Subsysteme `axes` ab
+    /// (ohne registriertes Subsystem gilt Kanal 0)
+    fn vote_axes(&mut self, axes: &[&str; 3], channels: [Vector3<f64>; 3]) -> Vector3<f64> {
+        let mut voted = channels[0];
+        for (axis, name) in axes.iter().enumerate() {
+            let values = channels.map(|channel| channel[axis]);
+            if let Some(value) = self.fdir.feed_subsystem(name, values) {
+                voted[axis] = value;
+            }
+        }
+        voted
+    }
+
+    /// Endlichkeitsprüfung des Zustands; bei NaN/∞ Diagnose mit Schrittnummer
+    /// und letztem gültigen Zustand
+    fn check_finite(&mut self) -> bool {
+        if self.state.is_finite() {
+            self.last_good_state = self.state.clone();
+            return true;
+        }
+        let good = &self.last_good_state;
+        self.logger.log(&format!(
+            "❌ Mission aborted: NaN/Inf in state at step {} (T+{:.1}s)",
+            self.iteration, self.state.time
+        ));
+        self.logger.log(&format!(
+            "   Last good state T+{:.1}s: pos=[{:.0}, {:.0}, {:.0}]m vel=[{:.1}, {:.1}, {:.1}]m/s \
+             mass={:.0}kg",
+            good.time,
+            good.position.x,
+            good.position.y,
+            good.position.z,
+            good.velocity.x,
+            good.velocity.y,
+            good.velocity.z,
+            good.mass
+        ));
+        self.telemetry.log_event(
+            SubsystemId::GNC,
+            2012,
+            EventSeverity::Critical,
+            &format!("Non-finite state at step {}", self.iteration),
+        );
+        false
+    }
+
+    /// Führt genau einen Zeitschritt `dt` aus
+    /// (FDIR, Guidance, Integration, Kalman-Filter, Telemetrie)
+    pub fn step(&mut self) -> StepOutcome {
+        if self.state.time >= self.config.max_time {
+            return StepOutcome::TimeLimit;
+        }
+        if !self.check_finite() {
+            return StepOutcome::NonFiniteState;
+        }
+
+        // Zielkörper entlang der Ephemeride bewegen (konstant über den Schritt)
+        if let Some(ephemeris) = &self.config.moon_ephemeris {
+            self.moon_pos = ephemeris.position_at(self.state.time);
+        }
+
+        // Telemetrie und Watchdog laufen auf Missionszeit
+        self.telemetry.set_mission_time(self.state.time);
+        self.fdir.set_mission_time(self.state.time);
+
+        // FDIR-Zyklus
+        let faults_before = self.fdir.fault_count;
+        self.fdir.run_cycle(self.logger.as_mut());
+        self.forward_fdir_events();
+        if self.fdir.fault_count > faults_before {
+            self.on_fault();
+        }
+        if !self.fdir.is_operational() {
+            self.logger.log("❌ Mission aborted: System critical failure");
+            return StepOutcome::SystemFailure;
+        }
+
+        // Schub berechnen (Burn-Zeitplan oder Guidance)
+        let thrust = match &self.config.scripted_burns {
+            Some(schedule) => schedule.thrust_at(self.state.time),
+            None => self.guidance_thrust(),
+        };
+        let thrust = self.vote_flight_computer(&thrust);
+
+        // Reserve gilt für die Planung als leer: keine regulären Burns mehr
+        let mass_floor = self.usable_mass_floor();
+        let thrust = if thrust.norm() > 0.0 && self.state.mass <= mass_floor {
+            self.note_reserve_reached();
+            Vector3::zeros()
+        } else {
+            thrust
+        };
+
+        let burning = thrust.norm() > 0.0;
+        let burn_label = format!("{:?}", self.guidance.phase());
+        self.timeline.record_engine(self.state.time, burning, &burn_label);
+
+        // Schubgrenze des aktiven Triebwerks (z.B. für geskriptete Burns)
+        let max_thrust = self.engine_max_thrust();
+        let thrust = if thrust.norm() > max_thrust {
+            thrust.normalize() * max_thrust
+        } else {
+            thrust
+        };
+
+        // Schubaufbau/-abfall; Massenstrom folgt dem tatsächlichen Schub
+        let thrust = self.thrust_transient(&thrust);
+
+        // Lageregelung: Schubachse auf Schubrichtung ausrichten
+        let commanded = thrust;
+        let thrust = self.update_attitude(&commanded);
+        self.pointing_error = self.thrust_pointing_error(&commanded);
 
-            // Physik-Integration (RK4)
-            physics::integrate_rk4(
+        // Physik-Integration, Guidance-Schritt in Teilschritte zerlegt
+        let bodies = self.bodies();
+        let substeps = self.config.physics_substeps.max(1);
+        let (isp, burnout_mass) = (self.engine_isp(), mass_floor);
+        let dt = self.config.dt / substeps as f64;
+        let (start_time, start_mass) = (self.state.time, self.state.mass);
+        let mut burn_delta_v = 0.0;
+        let propagator = Propagator {
+            integrator: self.config.integrator,
+            forces: self.config.forces,
+            central_body: 0, // Erde, siehe `bodies()`
+            frame: self.frame,
+            constants: self.config.constants,
+        };
+        for _ in 0..substeps {
+            let propellant = self.state.mass - burnout_mass;
+            let constants = &self.config.constants;
+            let applied = constants.available_thrust(&thrust, isp, propellant, dt).norm();
+            let previous = self.state.clone();
+            physics::integrate_bodies(
                 &mut self.state,
-                &self.earth_pos,
-                &self.moon_pos,
+                &bodies,
                 &thrust,
-                self.config.isp,
-                self.config.dt,
+                isp,
+                burnout_mass,
+                &propagator,
+                dt,
             );
-
-            // Kalman-Filter Update
-            self.kalman.predict(self.config.dt);
-            let noisy_pos = add_sensor_noise(&self.state.position, 100.0);
-            self.kalman.update(&noisy_pos);
-
-            // Telemetrie
-            if self.state.time - last_telemetry >= self.config.telemetry_interval {
-                self.log_telemetry();
-                last_telemetry = self.state.time;
+            // Oberflächenkontakt innerhalb des Teilschritts: Zustand am Kontaktpunkt
+            let contact = physics::find_surface_contact(&previous, &self.state, &bodies);
+            let touched = contact.is_some();
+            if let Some(contact) = contact {
+                self.state = contact;
+            }
+            // |a_Schub|·dt mit der mittleren Masse des Teilschritts
+            let elapsed = self.state.time - previous.time;
+            burn_delta_v += applied * elapsed / (0.5 * (previous.mass + self.state.mass));
+            if touched {
+                break;
             }
+        }
+        if !self.check_finite() {
+            return StepOutcome::NonFiniteState;
+        }
+        self.account_burn(start_time, start_mass, burn_delta_v);
 
-            // Status-Ausgabe (alle 1000 Iterationen)
-            if iteration % 1000 == 0 {
-                self.print_status();
+        // Oberflächenkontakt (Erde oder Mond), Aufprallgeschwindigkeit inertial
+        let collision = physics::check_collision(&self.inertial_state(), &self.bodies());
+        if let Some(collision) = collision {
+            let survivable = self.config.survivable_impact_speed;
+            if collision.body == self.config.target.name {
+                // Aufsetzen auf dem Mond ohne vorherige Landeerkennung der Guidance
+                let landing = LandingOutcome::classify(collision.impact_speed, survivable);
+                self.landing = Some(landing);
+                self.timeline.record(
+                    self.state.time,
+                    MilestoneKind::Touchdown,
+                    &format!("Surface contact at {:.1} m/s", collision.impact_speed),
+                );
+                if landing != LandingOutcome::Crash {
+                    self.guidance.set_phase(MissionPhase::Landed);
+                }
+            }
+            if collision.impact_speed > survivable {
+                self.logger.log(&format!(
+                    "💥 Mission failed: Impact on {} at {:.1} m/s",
+                    collision.body, collision.impact_speed
+                ));
+                return StepOutcome::Crashed;
             }
+        }
 
-            // FDIR nominal melden
-            self.fdir.report_nominal();
-
-            // Erfolgscheck
-            if self.guidance.phase == MissionPhase::Landed {
-                println!();
-                println!("✅ MISSION SUCCESS!");
-                return SimResult {
-                    success: true,
-                    final_state: self.state.clone(),
-                    mission_time: self.state.time,
-                    fuel_used: initial_mass - self.state.mass,
-                    telemetry: std::mem::take(&mut self.telemetry),
-                };
+        // Kalman-Filter: Predict je Schritt, Update nur bei fälliger Messung
+        self.kalman.predict(self.config.dt);
+        let was_diverged = self.kalman.is_diverged();
+        if self.measurement_due() {
+            self.last_measurement = Some(self.state.time);
+            let voted_pos = self.vote_imu();
+            match self.kalman.update(&voted_pos) {
+                Ok(()) => {}
+                Err(err @ KalmanError::OutlierRejected { .. }) => {
+                    self.logger.log(&format!("⚠️ NAV: Measurement rejected ({})", err));
+                    self.telemetry.log_event(
+                        SubsystemId::GNC,
+                        2014,
+                        EventSeverity::Warning,
+                        "Navigation measurement rejected",
+                    );
+                }
+                Err(err) => {
+                    self.logger.log(&format!("⚠️ NAV: Kalman update skipped ({})", err));
+                    self.telemetry.log_event(
+                        SubsystemId::GNC,
+                        2009,
+                        EventSeverity::Warning,
+                        "Navigation update skipped",
+                    );
+                }
             }
+        }
+        if self.kalman.is_diverged() && !was_diverged {
+            self.logger.log(&format!(
+                "⚠️ NAV: Kalman filter diverged (NIS {:.1})",
+                self.kalman.last_nis
+            ));
+            self.telemetry.log_event(
+                SubsystemId::GNC,
+                2004,
+                EventSeverity::Warning,
+                "Navigation filter diverged",
+            );
+        }
 
-            // Treibstoff-Check
-            if self.state.mass <= self.config.dry_mass {
-                println!("⛽ Mission failed: Out of fuel!");
-                break;
+        // Telemetrie
+        if self.state.time - self.last_telemetry >= self.config.telemetry_interval {
+            self.log_telemetry();
+            if self.config.record_trajectory {
+                self.trajectory.push(self.state.clone());
+                self.trajectory_phases.push(self.guidance.phase());
             }
+            self.last_telemetry = self.state.time;
+        }
 
-            iteration += 1;
+        // Status-Ausgabe (alle 1000 Iterationen)
+        if self.iteration.is_multiple_of(1000) {
+            self.print_status();
         }
+        self.iteration += 1;
 
-        SimResult {
-            success: false,
-            final_state: self.state.clone(),
-            mission_time: self.state.time,
-            fuel_used: initial_mass - self.state.mass,
-            telemetry: std::mem::take(&mut self.telemetry),
+        // FDIR nominal melden
+        self.fdir.report_nominal(self.logger.as_mut());
+        self.forward_fdir_events();
+
+        // Erfolgscheck
+        if self.guidance.phase() == MissionPhase::Landed {
+            self.logger.log("");
+            self.logger.log("✅ MISSION SUCCESS!");
+            return StepOutcome::Landed;
+        }
+
+        // Treibstoff-Check: ausgebrannte Unterstufe abtrennen, sonst Missionsende;
+        // außerhalb des Abbruchs ist mit der Reserve der nutzbare Treibstoff erschöpft
+        let mass_floor = self.usable_mass_floor();
+        if self.state.mass <= mass_floor && !self.stage() {
+            if mass_floor > self.burnout_mass() {
+                self.note_reserve_reached();
+            }
+            self.logger.log("⛽ Mission failed: Out of fuel!");
+            return StepOutcome::OutOfFuel;
+        }
+
+        // Fluchtbahn: Erde verlassen ohne Einfang durch den Zielkörper
+        if self.is_escaping() {
+            let [earth, _] = self.bodies();
+            self.logger.log(&format!(
+                "🌌 Mission failed: Earth escape trajectory (ε = {:.0} J/kg, r = {:.0} km)",
+                physics::specific_energy(&self.state, &earth),
+                (self.state.position - self.earth_pos).norm() / 1000.0
+            ));
+            self.telemetry.log_event(
+                SubsystemId::GNC,
+                2008,
+                EventSeverity::Error,
+                "Earth escape trajectory",
+            );
+            return StepOutcome::Escaped;
         }
+
+        StepOutcome::Running
     }
 
     fn log_telemetry(&mut self) {
         let pos = self.state.position;
         let vel = self.state.velocity;
-        self.telemetry
-            .log_navigation([pos.x, pos.y, pos.z], [vel.x, vel.y, vel.z]);
+        self.telemetry.set_mission_time(self.state.time);
+        let frame = match self.guidance.phase() {
+            MissionPhase::LunarOrbitInsertion | MissionPhase::Descent
+                if self.config.lunar_frame_telemetry =>
+            {
+                ReferenceFrame::MoonCentered
+            }
+            _ => ReferenceFrame::EarthCentered,
+        };
+        let moon_velocity = match &self.config.moon_ephemeris {
+            Some(ephemeris) => ephemeris.velocity_at(self.state.time),
+            None => Vector3::zeros(),
+        };
+        let (position, velocity) =
+            frame.from_earth_centered(&pos, &vel, &self.moon_pos, &moon_velocity);
+        self.telemetry.log_target(self.moon_pos.into(), moon_velocity.into());
+        self.telemetry.log_navigation_in(frame, position, velocity);
 
-        let fuel_percent =
-            (self.state.mass - self.config.dry_mass) / (self.config.initial_mass - self.config.dry_mass) * 100.0;
+        let fuel_percent = self.fuel_percent();
 
         self.telemetry.log_status(
-            self.guidance.phase as u8,
+            self.guidance.phase() as u8,
             fuel_percent as f32,
             if self.fdir.is_operational() { 100 } else { 0 },
+            self.remaining_delta_v() as f32,
+        );
+
+        // Umgebung (Temperatur, Druck, Strahlung) am geozentrischen Ort
+        let (temperature, pressure, radiation) = physics::environment_at(
+            &(pos - self.earth_pos),
+            self.state.time,
+            &physics::sun_position(self.state.time),
         );
+        self.telemetry.log_sensors(temperature, pressure, radiation);
+
+        // Energie-Diagnose: auf antriebslosen Bögen nahezu konstant
+        let potential = self.config.constants.gravitational_potential(&pos, &self.bodies());
+        let energy = 0.5 * vel.norm_squared() + potential;
+        let position_uncertainty = self.kalman.position_uncertainty() as f32;
+        self.telemetry
+            .log_diagnostics(energy, self.pointing_error as f32, position_uncertainty);
+
+        // Lage des Fahrzeugs laut Lageregler
+        let q = self.attitude.orientation.quaternion();
+        let rate = self.attitude.angular_velocity;
+        self.telemetry.log_attitude([q.w, q.i, q.j, q.k], [rate.x, rate.y, rate.z]);
     }
 
-    fn print_status(&self) {
-        let distance_earth = self.state.position.norm();
+    fn print_status(&mut self) {
+        let altitude_earth = self.state.altitude_above(&self.earth_pos, R_EARTH);
         let distance_moon = (self.moon_pos - self.state.position).norm();
-        let speed = self.state.velocity.norm();
-        let fuel_percent =
-            (self.state.mass - self.config.dry_mass) / (self.config.initial_mass - self.config.dry_mass) * 100.0;
+        let speed = self.state.speed();
+        let fuel_percent = self.fuel_percent();
 
-        println!(
+        self.logger.log(&format!(
             "T+{:>8.0}s | Phase: {:?} | Alt Earth: {:>10.0}km | Dist Moon: {:>10.0}km | Speed: {:>8.1}m/s | Fuel: {:>5.1}%",
             self.state.time,
-            self.guidance.phase,
-            (distance_earth - 6.371e6) / 1000.0,
+            self.guidance.phase(),
+            altitude_earth / 1000.0,
             distance_moon / 1000.0,
             speed,
             fuel_percent
-        );
+        ));
     }
 }
 
+/// Wartezeit bis zur Soll-Wall-Clock-Zeit `sim_elapsed / scale`
+/// (None, wenn die Simulation hinterherhinkt)
+fn pacing_delay(sim_elapsed: f64, scale: f64, wall_elapsed: Duration) -> Option<Duration> {
+    Duration::from_secs_f64(sim_elapsed / scale).checked_sub(wall_elapsed)
+}
+
 /// Schnellstart-Funktion
 pub fn run_moon_mission() -> SimResult {
     let config = SimConfig::default();
@@ -239,9 +1472,73 @@ pub fn run_moon_mission() -> SimResult {
     sim.run()
 }
 
-#[cfg(test)]
+/// Headless-Einstiegspunkt: führt die Mission ohne jede Konsolenausgabe durch
+///
+/// Geeignet für Aufrufe aus anderem Rust-Code oder WASM.
+pub fn simulate(config: &SimConfig) -> SimResult {
+    simulate_with_logger(config, Box::new(NullLogger))
+}
+
+/// Wie `simulate`, aber mit eigenem Ziel für Status- und Phasenmeldungen
+pub fn simulate_with_logger(config: &SimConfig, logger: Box<dyn MissionLogger>) -> SimResult {
+    let mut sim = MoonMissionSim::new(config.clone()).with_logger(logger);
+    sim.run()
+}
+
+/// Parameterstudie: simuliert jede Kombination aus Schub [N] und Isp [s]
+///
+/// Die Läufe starten von `base` aus und laufen headless auf einem Pool von
+/// höchstens `available_parallelism()` Threads, die sich die Gitterpunkte
+/// der Reihe nach holen. Ergebnis in Gitterreihenfolge (Schub außen, Isp
+/// innen) als `(max_thrust, isp, SimResult)`.
+pub fn parameter_sweep(
+    base: &SimConfig,
+    thrust_values: &[f64],
+    isp_values: &[f64],
+) -> Vec<(f64, f64, SimResult)> {
+    let grid: Vec<(f64, f64)> = thrust_values
+        .iter()
+        .flat_map(|&thrust| isp_values.iter().map(move |&isp| (thrust, isp)))
+        .collect();
+
+    let workers = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(grid.len());
+    let next = AtomicUsize::new(0);
+
+    let mut results: Vec<(usize, (f64, f64, SimResult))> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(&(thrust, isp)) = grid.get(index) else {
+                            break done;
+                        };
+                        let config = SimConfig { max_thrust: thrust, isp, ..base.clone() };
+                        done.push((index, (thrust, isp, simulate(&config))));
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("Simulationslauf abgebrochen"))
+            .collect()
+    });
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fdir::SystemStatus;
+    use crate::gnc::ABORT_ALTITUDE_TARGET;
+    use crate::physics::{G, M_EARTH};
+    use crate::telemetry::{CaptureLogger, TelemetryPayload};
+    use std::sync::{Arc, Mutex};
 
     #[test]
     fn test_sim_initialization() {
@@ -252,6 +1549,49 @@ mod tests {
         assert!(sim.state.position.norm() > 0.0);
     }
 
+    #[test]
+    fn test_initial_tumble_detumbled() {
+        let initial = InitialAttitude {
+            euler: [0.4, -0.3, 1.2],
+            angular_velocity: [0.2, -0.1, 0.15],
+        };
+        let mut sim = MoonMissionSim::new(SimConfig {
+            quiet: true,
+            initial_attitude: Some(initial),
+            scripted_burns: Some(BurnSchedule::new()),
+            ..Default::default()
+        });
+        let initial_rate = sim.attitude.angular_velocity.norm();
+        let [roll, pitch, yaw] = initial.euler;
+        let start = AttitudeController::from_euler(roll, pitch, yaw);
+        assert_eq!(sim.attitude.orientation, start);
+
+        let mut rates = Vec::new();
+        for _ in 0..30 {
+            sim.step();
+            rates.push(sim.attitude.angular_velocity.norm());
+        }
+        assert!(rates[9] < initial_rate);
+        assert!(rates[29] < 1e-3 * initial_rate, "{} rad/s", rates[29]);
+        assert!(sim.attitude.orientation.angle_to(&start) < 0.01);
+    }
+
+    #[test]
+    fn test_states_iterator() {
+        let mut sim = MoonMissionSim::new(SimConfig {
+            dt: 10.0,
+            max_time: 100.0,
+            quiet: true,
+            ..Default::default()
+        });
+        let states: Vec<SpacecraftState> = sim.states().collect();
+
+        assert_eq!(states.len(), 10);
+        assert_eq!(states[0].time, 10.0);
+        assert_eq!(states.last().unwrap().time, 100.0);
+        assert_eq!(sim.step(), StepOutcome::TimeLimit);
+    }
+
     #[test]
     fn test_short_simulation() {
         let config = SimConfig {
@@ -265,5 +1605,1364 @@ mod tests {
         // Sollte noch nicht gelandet sein
         assert!(!result.success);
         assert!(result.mission_time >= 100.0);
+        assert!(result.trajectory.is_empty()); // Standard: keine Aufzeichnung
+    }
+
+    #[test]
+    fn test_trajectory_recording() {
+        let config = SimConfig {
+            dt: 10.0,
+            max_time: 600.0,
+            telemetry_interval: 60.0,
+            record_trajectory: true,
+            ..Default::default()
+        };
+        let mut sim = MoonMissionSim::new(config);
+        let result = sim.run();
+
+        assert!(!result.trajectory.is_empty());
+        assert!(result.trajectory.windows(2).all(|w| w[1].time > w[0].time));
+    }
+
+    #[test]
+    fn test_trajectory_ndjson_export() {
+        let result = simulate(&SimConfig {
+            dt: 10.0,
+            max_time: 600.0,
+            telemetry_interval: 60.0,
+            record_trajectory: true,
+            ..Default::default()
+        });
+        let path = std::env::temp_dir().join("azb_trajectory_export.ndjson");
+        result.export_trajectory_ndjson(&path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), result.trajectory.len());
+        assert!(!lines.is_empty());
+        for (line, state) in lines.iter().zip(&result.trajectory) {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            for key in ["time", "position", "velocity", "mass", "phase"] {
+                assert!(value.get(key).is_some(), "{} fehlt", key);
+            }
+            assert_eq!(value["time"], state.time);
+            assert_eq!(value["position"][0], state.position.x);
+        }
+    }
+
+    #[test]
+    fn test_step_matches_run() {
+        let dt = 10.0;
+        let mut stepped = MoonMissionSim::new(SimConfig {
+            dt,
+            ..Default::default()
+        });
+        for _ in 0..100 {
+            assert_eq!(stepped.step(), StepOutcome::Running);
+        }
+
+        let mut full = MoonMissionSim::new(SimConfig {
+            dt,
+            max_time: 100.0 * dt,
+            ..Default::default()
+        });
+        let result = full.run();
+
+        assert_eq!(result.outcome, StepOutcome::TimeLimit);
+        assert_eq!(stepped.state.time, result.final_state.time);
+        assert_eq!(stepped.state.position, result.final_state.position);
+        assert_eq!(stepped.state.velocity, result.final_state.velocity);
+        assert_eq!(stepped.state.mass, result.final_state.mass);
+    }
+
+    #[test]
+    fn test_capture_logger() {
+        let capture = CaptureLogger::new();
+        let config = SimConfig {
+            dt: 10.0,
+            max_time: 3600.0,
+            ..Default::default()
+        };
+        let mut sim = MoonMissionSim::new(config).with_logger(Box::new(capture.clone()));
+        sim.run();
+
+        let tli_messages = capture
+            .messages()
+            .iter()
+            .filter(|m| m.contains("TLI Burn complete"))
+            .count();
+        assert_eq!(tli_messages, 1);
+    }
+
+    #[test]
+    fn test_phase_change_callback() {
+        let transitions = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&transitions);
+
+        let config = SimConfig {
+            dt: 10.0,
+            max_time: 100.0,
+            quiet: true,
+            ..Default::default()
+        };
+        let mut sim = MoonMissionSim::new(config).with_phase_callback(Box::new(
+            move |from, to, _state: &SpacecraftState| recorded.lock().unwrap().push((from, to)),
+        ));
+        // Raumschiff in Mondnähe platzieren → Wechsel TLI → LOI
+        sim.state.position = sim.moon_pos - Vector3::new(50_000_000.0, 0.0, 0.0);
+        sim.run();
+
+        let transitions = transitions.lock().unwrap();
+        assert_eq!(
+            transitions[0],
+            (
+                MissionPhase::TransLunarInjection,
+                MissionPhase::LunarOrbitInsertion
+            )
+        );
+    }
+
+    #[test]
+    fn test_parameter_sweep_grid() {
+        let base = SimConfig {
+            dt: 10.0,
+            max_time: 300.0,
+            quiet: true,
+            ..Default::default()
+        };
+        let results = parameter_sweep(&base, &[80_000.0, 120_000.0], &[300.0, 450.0]);
+
+        let params: Vec<(f64, f64)> = results.iter().map(|(t, isp, _)| (*t, *isp)).collect();
+        assert_eq!(
+            params,
+            vec![(80_000.0, 300.0), (80_000.0, 450.0), (120_000.0, 300.0), (120_000.0, 450.0)]
+        );
+        // Höherer Isp → weniger Treibstoff für denselben Schub
+        assert!(results[1].2.fuel_used < results[0].2.fuel_used);
+        assert!(results.iter().all(|(_, _, r)| r.mission_time > 0.0));
+    }
+
+    #[test]
+    fn test_parameter_sweep_more_points_than_workers() {
+        let base = SimConfig {
+            dt: 10.0,
+            max_time: 30.0,
+            quiet: true,
+            ..Default::default()
+        };
+        let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let thrusts: Vec<f64> = (1..=workers + 2).map(|i| i as f64 * 10_000.0).collect();
+        let isps = [300.0, 450.0, 600.0];
+        let results = parameter_sweep(&base, &thrusts, &isps);
+
+        assert_eq!(results.len(), thrusts.len() * isps.len());
+        for (i, (thrust, isp, _)) in results.iter().enumerate() {
+            assert_eq!((*thrust, *isp), (thrusts[i / isps.len()], isps[i % isps.len()]));
+        }
+        assert!(parameter_sweep(&base, &[], &isps).is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let config = SimConfig {
+            dt: 10.0,
+            quiet: true,
+            ..Default::default()
+        };
+        let mut sim = MoonMissionSim::new(config);
+        let snapshot = sim.snapshot();
+
+        for _ in 0..50 {
+            sim.step();
+        }
+        let first = sim.state.clone();
+
+        sim.restore(snapshot);
+        assert_eq!(sim.state.time, 0.0);
+        for _ in 0..50 {
+            sim.step();
+        }
+
+        assert_eq!(sim.state.time, first.time);
+        assert_eq!(sim.state.position, first.position);
+        assert_eq!(sim.state.velocity, first.velocity);
+        assert_eq!(sim.state.mass, first.mass);
+    }
+
+    #[test]
+    fn test_configurable_start_orbit() {
+        let low = MoonMissionSim::new(SimConfig::default());
+        let high = MoonMissionSim::new(SimConfig {
+            start_altitude: 800_000.0,
+            ..Default::default()
+        });
+
+        assert!((high.state.position.norm() - (R_EARTH + 800_000.0)).abs() < 1e-6);
+        assert!(high.state.position.norm() > low.state.position.norm());
+        assert!(high.state.velocity.norm() < low.state.velocity.norm());
+        // Kreisbahn: v = sqrt(μ/r) ≈ 7.45 km/s in 800km Höhe
+        assert!((high.state.velocity.norm() - 7_452.0).abs() < 5.0);
+
+        let inclined = MoonMissionSim::new(SimConfig {
+            start_inclination: std::f64::consts::FRAC_PI_2,
+            start_phase_angle: 0.0,
+            ..Default::default()
+        });
+        // Polbahn: Geschwindigkeit entlang Z
+        assert!(inclined.state.velocity.z > 7_000.0);
+    }
+
+    #[test]
+    fn test_headless_simulate() {
+        let config = SimConfig {
+            dt: 1.0,
+            max_time: 3600.0,
+            telemetry_interval: 60.0,
+            ..Default::default()
+        };
+
+        let result = simulate(&config);
+        assert_eq!(result.outcome, StepOutcome::TimeLimit);
+        assert!(result.fuel_used > 0.0);
+        assert!(!result.telemetry.get_packets().is_empty());
+
+        // Alle Meldungen laufen über den Logger, keine direkte stdout-Ausgabe
+        let capture = CaptureLogger::new();
+        let captured = simulate_with_logger(&config, Box::new(capture.clone()));
+        assert!(capture.messages().iter().any(|m| m.contains("TLI Burn complete")));
+        assert_eq!(captured.final_state.position, result.final_state.position);
+    }
+
+    #[test]
+    fn test_realtime_pacing() {
+        // Soll-Wall-Clock aus der Sim-Zeit: 200 s bei 1000-facher Geschwindigkeit = 0.2 s
+        let ms = Duration::from_millis;
+        assert_eq!(pacing_delay(200.0, 1000.0, ms(50)), Some(ms(150)));
+        assert_eq!(pacing_delay(200.0, 1000.0, ms(200)), Some(Duration::ZERO));
+        assert_eq!(pacing_delay(200.0, 1000.0, ms(250)), None); // hinkt hinterher
+
+        // Im Lauf: nie schneller als die Sim-Zeit erlaubt (obere Schranke hängt
+        // von der Rechnerlast ab und wird nicht geprüft)
+        let config = SimConfig {
+            dt: 1.0,
+            max_time: 200.0,
+            quiet: true,
+            realtime_scale: Some(1000.0),
+            ..Default::default()
+        };
+        let start = Instant::now();
+        let result = MoonMissionSim::new(config).run();
+        let elapsed = start.elapsed().as_secs_f64();
+
+        assert!(elapsed >= (result.mission_time - 1.0) / 1000.0, "zu schnell: {}s", elapsed);
+    }
+
+    #[test]
+    fn test_lunar_crash() {
+        let config = SimConfig {
+            dt: 1.0,
+            max_time: 600.0,
+            quiet: true,
+            ..Default::default()
+        };
+        let mut sim = MoonMissionSim::new(config);
+        // 2 km über dem Mond, 500 m/s senkrecht nach unten
+        sim.state.position = sim.moon_pos - Vector3::new(physics::R_MOON + 2_000.0, 0.0, 0.0);
+        sim.state.velocity = Vector3::new(500.0, 0.0, 0.0);
+
+        let result = sim.run();
+        assert_eq!(result.outcome, StepOutcome::Crashed);
+        assert_eq!(result.landing, Some(LandingOutcome::Crash));
+        assert!(!result.success);
+        // Aufschlag an der Oberfläche, nicht erst am Ende des Schritts darunter
+        let altitude = (result.final_state.position - sim.moon_pos).norm() - physics::R_MOON;
+        assert!(altitude <= 0.0 && altitude > -1e-3, "{} m", altitude);
+        assert!(result.mission_time.fract() > 0.0);
+    }
+
+    #[test]
+    fn test_terrain_height_reaches_collision() {
+        let crater_rim = |_latitude: f64, _longitude: f64| 500.0;
+        let config = SimConfig {
+            dt: 1.0,
+            max_time: 600.0,
+            quiet: true,
+            target: TargetBody {
+                terrain_height: crater_rim,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut sim = MoonMissionSim::new(config);
+        let guidance = sim.guidance.as_any().downcast_ref::<GuidanceComputer>().unwrap();
+        assert_eq!((guidance.terrain_height)(0.0, 0.0), 500.0);
+        sim.state.position = sim.moon_pos - Vector3::new(physics::R_MOON + 2_000.0, 0.0, 0.0);
+        sim.state.velocity = Vector3::new(500.0, 0.0, 0.0);
+
+        // Aufschlag auf dem Kraterrand, 500 m über dem Referenzradius
+        let result = sim.run();
+        assert_eq!(result.outcome, StepOutcome::Crashed);
+        let altitude = (result.final_state.position - sim.moon_pos).norm() - physics::R_MOON;
+        assert!((altitude - 500.0).abs() < 1e-2, "{} m", altitude);
+    }
+
+    #[test]
+    fn test_soft_landing() {
+        let config = SimConfig {
+            dt: 1.0,
+            max_time: 600.0,
+            quiet: true,
+            ..Default::default()
+        };
+        let mut sim = MoonMissionSim::new(config);
+        // 5 m über dem Mond, 1 m/s Sinkrate im Abstieg
+        sim.guidance.set_phase(MissionPhase::Descent);
+        sim.state.position = sim.moon_pos - Vector3::new(physics::R_MOON + 5.0, 0.0, 0.0);
+        sim.state.velocity = Vector3::new(1.0, 0.0, 0.0);
+
+        let result = sim.run();
+        assert_eq!(result.outcome, StepOutcome::Landed);
+        assert_eq!(result.landing, Some(LandingOutcome::Soft));
+        assert!(result.success);
+    }
+
+    #[test]
+    fn test_abort_during_descent() {
+        let config = SimConfig {
+            dt: 1.0,
+            max_time: 900.0,
+            quiet: true,
+            ..Default::default()
+        };
+        let mut sim = MoonMissionSim::new(config);
+        // 100 km über dem Mond, suborbital (Periapsis unter der Oberfläche), sinkend
+        sim.guidance.set_phase(MissionPhase::Descent);
+        sim.state.position = sim.moon_pos - Vector3::new(physics::R_MOON + 100_000.0, 0.0, 0.0);
+        sim.state.velocity = Vector3::new(20.0, 1_200.0, 0.0);
+
+        let mu = sim.config.target.mu;
+        let periapsis = |state: &SpacecraftState, moon: Vector3<f64>| {
+            let r = state.position - moon;
+            let energy = 0.5 * state.velocity.norm_squared() - mu / r.norm();
+            let h = r.cross(&state.velocity).norm();
+            let a = -mu / (2.0 * energy);
+            let e = (1.0 + 2.0 * energy * h * h / (mu * mu)).sqrt();
+            a * (1.0 - e)
+        };
+        let initial_periapsis = periapsis(&sim.state, sim.moon_pos);
+        assert!(initial_periapsis < physics::R_MOON);
+
+        sim.inject_fault("Injected descent fault");
+        assert_eq!(sim.guidance.phase(), MissionPhase::Abort);
+        let events: Vec<u16> = sim
+            .telemetry
+            .get_packets()
+            .iter()
+            .filter_map(|p| match p.payload {
+                TelemetryPayload::Event { event_code, .. } => Some(event_code),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(events, vec![2001, 2006, 2003]);
+
+        let result = sim.run();
+        assert_eq!(result.outcome, StepOutcome::TimeLimit);
+        let radial = (result.final_state.position - sim.moon_pos).normalize();
+        assert!(result.final_state.velocity.dot(&radial) > -1.0); // sinkt nicht mehr
+        let final_periapsis = periapsis(&result.final_state, sim.moon_pos);
+        assert!(final_periapsis > physics::R_MOON + ABORT_ALTITUDE_TARGET * 0.5);
+    }
+
+    /// Guidance ohne Schub (reiner Freiflug)
+    #[derive(Debug, Clone)]
+    struct CoastGuidance;
+
+    impl Guidance for CoastGuidance {
+        fn compute_thrust(
+            &mut self,
+            _state: &SpacecraftState,
+            _bodies: &[GravBody],
+            _log: &mut dyn MissionLogger,
+        ) -> Vector3<f64> {
+            Vector3::zeros()
+        }
+
+        fn phase(&self) -> MissionPhase {
+            MissionPhase::TransLunarInjection
+        }
+
+        fn set_phase(&mut self, _phase: MissionPhase) {}
+    }
+
+    #[test]
+    fn test_custom_guidance_coasts() {
+        let config = SimConfig {
+            dt: 1.0,
+            max_time: 600.0,
+            quiet: true,
+            ..Default::default()
+        };
+        let mut sim = MoonMissionSim::new(config.clone()).with_guidance(Box::new(CoastGuidance));
+        let mut expected = sim.state.clone();
+        let bodies = sim.bodies();
+        let result = sim.run();
+
+        for _ in 0..600 {
+            physics::integrate_rk4_bodies(
+                &mut expected,
+                &bodies,
+                &Vector3::zeros(),
+                config.isp,
+                config.dry_mass,
+                config.dt,
+            );
+        }
+        assert_eq!(result.final_state.position, expected.position);
+        assert_eq!(result.fuel_used, 0.0);
+    }
+
+    #[test]
+    fn test_rotating_frame_holds_l4() {
+        let d = physics::EARTH_MOON_DISTANCE;
+        let l4 = Vector3::new(d / 2.0, d * 3f64.sqrt() / 2.0, 0.0);
+        let drift = |rotating_frame: bool| {
+            let mut sim = MoonMissionSim::new(SimConfig {
+                dt: 60.0,
+                rotating_frame,
+                quiet: true,
+                scripted_burns: Some(BurnSchedule::new()),
+                ..Default::default()
+            });
+            sim.state.position = l4;
+            sim.state.velocity = Vector3::zeros();
+            for _ in 0..20 {
+                sim.step();
+            }
+            (sim.state.position - l4).norm()
+        };
+
+        // L4 ruht im mitrotierenden System, inertial fällt der Körper zur Erde
+        assert!(drift(true) < 1.0);
+        assert!(drift(false) > 1_000.0);
+    }
+
+    #[test]
+    fn test_rotating_frame_guidance_inertial() {
+        let thrust_in = |rotating_frame: bool| {
+            let mut sim = MoonMissionSim::new(SimConfig {
+                rotating_frame,
+                quiet: true,
+                ..Default::default()
+            });
+            sim.guidance_thrust()
+        };
+
+        // Gleicher physikalischer Startzustand: gleiches Schubkommando
+        let (inertial, rotating) = (thrust_in(false), thrust_in(true));
+        assert!(inertial.norm() > 0.0);
+        assert!((rotating - inertial).norm() < 1e-6 * inertial.norm(), "{:?}", rotating);
+    }
+
+    #[test]
+    fn test_thrust_pointing_error_telemetry() {
+        let mut sim = MoonMissionSim::new(SimConfig {
+            quiet: true,
+            ..Default::default()
+        });
+        let commanded = Vector3::new(100_000.0, 0.0, 0.0);
+
+        sim.attitude.point_towards(&commanded);
+        sim.attitude.orientation = sim.attitude.target_orientation;
+        assert!(sim.thrust_pointing_error(&commanded) < 1e-9);
+
+        // Schubachse +z, Kommando entlang +x: 90° Fehler
+        sim.attitude.orientation = nalgebra::UnitQuaternion::identity();
+        sim.pointing_error = sim.thrust_pointing_error(&commanded);
+        assert!((sim.pointing_error - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+
+        sim.log_telemetry();
+        let reported = sim.telemetry.get_packets().iter().rev().find_map(|p| match p.payload {
+            TelemetryPayload::Diagnostics { pointing_error, .. } => Some(pointing_error),
+            _ => None,
+        });
+        assert!((reported.unwrap() - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_tmr_masks_corrupted_imu_channel() {
+        let config = SimConfig {
+            max_time: 200.0,
+            quiet: true,
+            ..Default::default()
+        };
+        let run = |corrupt: bool| {
+            let mut sim = MoonMissionSim::new(config.clone());
+            for step in 0..100 {
+                if corrupt && step == 50 {
+                    sim.imu_channel_error[1] = Vector3::new(25_000.0, -8_000.0, 3_000.0);
+                }
+                sim.step();
+            }
+            sim
+        };
+
+        let nominal = run(false);
+        let corrupted = run(true);
+        let imu_status = |sim: &MoonMissionSim| -> Vec<SystemStatus> {
+            let imus = sim.fdir.subsystems.iter().filter(|s| IMU_AXES.contains(&s.name.as_str()));
+            imus.map(|s| s.status).collect()
+        };
+        // Unabhängig verrauschte Kanäle bleiben innerhalb der Toleranz einig
+        assert_eq!(imu_status(&nominal), vec![SystemStatus::Nominal; 3]);
+        assert_eq!(imu_status(&corrupted), vec![SystemStatus::Warning; 3]);
+        // Median wählt einen gesunden Kanal: Abweichung nur im Rauschen
+        let deviation =
+            (corrupted.kalman.estimated_position() - nominal.kalman.estimated_position()).norm();
+        assert!(deviation < SENSOR_NOISE, "{} m", deviation);
+        assert!(corrupted.telemetry.get_packets().iter().any(|p| matches!(
+            p.payload,
+            TelemetryPayload::Event { event_code: 2013, .. }
+        )));
+    }
+
+    #[test]
+    fn test_tmr_masks_corrupted_flight_computer() {
+        let config = SimConfig {
+            max_time: 200.0,
+            quiet: true,
+            ..Default::default()
+        };
+        let run = |corrupt: bool| {
+            let mut sim = MoonMissionSim::new(config.clone());
+            for step in 0..100 {
+                if corrupt && step == 50 {
+                    sim.flight_computer_error[2] = Vector3::new(0.0, -60_000.0, 5_000.0);
+                }
+                sim.step();
+            }
+            sim
+        };
+
+        let nominal = run(false);
+        let corrupted = run(true);
+        assert_eq!(corrupted.state.position, nominal.state.position);
+        assert_eq!(corrupted.state.mass, nominal.state.mass);
+        let computers: Vec<SystemStatus> = corrupted
+            .fdir
+            .subsystems
+            .iter()
+            .filter(|s| FLIGHT_COMPUTER_AXES.contains(&s.name.as_str()))
+            .map(|s| s.status)
+            .collect();
+        assert_eq!(
+            computers,
+            vec![SystemStatus::Nominal, SystemStatus::Warning, SystemStatus::Warning]
+        );
+    }
+
+    #[test]
+    fn test_nan_state_aborts() {
+        let log = CaptureLogger::new();
+        let mut sim = MoonMissionSim::new(SimConfig {
+            max_time: 100.0,
+            ..Default::default()
+        })
+        .with_logger(Box::new(log.clone()));
+        for _ in 0..5 {
+            assert_eq!(sim.step(), StepOutcome::Running);
+        }
+        let good = sim.state.clone();
+
+        sim.state.velocity.y = f64::NAN;
+        assert_eq!(sim.step(), StepOutcome::NonFiniteState);
+        let messages = log.messages();
+        assert!(messages.iter().any(|m| m.contains("NaN/Inf in state at step 5")));
+        assert!(messages.iter().any(|m| m.contains(&format!("T+{:.1}s", good.time))));
+        assert!(sim.telemetry.get_packets().iter().any(|p| matches!(
+            p.payload,
+            TelemetryPayload::Event { event_code: 2012, .. }
+        )));
+    }
+
+    #[test]
+    fn test_moon_follows_ephemeris() {
+        let start = Vector3::new(physics::EARTH_MOON_DISTANCE, 0.0, 0.0);
+        let later = Vector3::new(physics::EARTH_MOON_DISTANCE, 1.0e7, 0.0);
+        let mut sim = MoonMissionSim::new(SimConfig {
+            dt: 10.0,
+            quiet: true,
+            moon_ephemeris: MoonEphemeris::new(vec![(0.0, start), (100.0, later)]),
+            ..Default::default()
+        });
+        assert_eq!(sim.moon_pos, start);
+
+        for _ in 0..6 {
+            sim.step();
+        }
+        assert_eq!(sim.moon_pos, start.lerp(&later, 0.5));
+    }
+
+    #[test]
+    fn test_thrust_transient_time_constant() {
+        let mut sim = MoonMissionSim::new(SimConfig {
+            dt: 1.0,
+            quiet: true,
+            thrust_tau: 10.0,
+            ..Default::default()
+        });
+        let full = sim.state.velocity.normalize() * sim.config.max_thrust;
+        sim.config.scripted_burns = Some(BurnSchedule::new().with_burn(0.0, 100.0, full));
+        let initial_mass = sim.state.mass;
+
+        sim.step();
+        let first_step_flow = initial_mass - sim.state.mass;
+        for _ in 1..10 {
+            sim.step();
+        }
+        // Nach einer Zeitkonstante: 1 - 1/e ≈ 63 % des Kommandos
+        let fraction = sim.actual_thrust.norm() / sim.config.max_thrust;
+        assert!((fraction - (1.0 - (-1.0f64).exp())).abs() < 1e-6, "{}", fraction);
+        // Massenstrom folgt dem tatsächlichen, nicht dem kommandierten Schub
+        let full_flow = physics::propellant_mass_flow(sim.config.max_thrust, sim.config.isp);
+        assert!(first_step_flow < 0.2 * full_flow, "{} kg", first_step_flow);
+    }
+
+    #[test]
+    fn test_thrust_transient_independent_of_dt() {
+        // Verbrauch in den ersten 10 s eines Burns mit τ = 5 s (Schubaufbau)
+        let propellant_used = |dt: f64| {
+            let mut sim = MoonMissionSim::new(SimConfig {
+                dt,
+                quiet: true,
+                thrust_tau: 5.0,
+                ..Default::default()
+            });
+            let full = sim.state.velocity.normalize() * sim.config.max_thrust;
+            sim.config.scripted_burns = Some(BurnSchedule::new().with_burn(10.0, 30.0, full));
+            let initial_mass = sim.state.mass;
+            while sim.state.time < 20.0 - 1e-9 {
+                assert_eq!(sim.step(), StepOutcome::Running);
+            }
+            initial_mass - sim.state.mass
+        };
+
+        let coarse = propellant_used(2.0);
+        let fine = propellant_used(0.25);
+        assert!((coarse - fine).abs() < 1e-3 * fine, "{} vs {} kg", coarse, fine);
+    }
+
+    #[test]
+    fn test_burn_report_matches_rocket_equation() {
+        let mut sim = MoonMissionSim::new(SimConfig {
+            dt: 1.0,
+            max_time: 100.0,
+            quiet: true,
+            ..Default::default()
+        });
+        let full = sim.state.velocity.normalize() * sim.config.max_thrust;
+        sim.config.scripted_burns = Some(BurnSchedule::new().with_burn(10.0, 30.0, full));
+        let initial_mass = sim.state.mass;
+
+        let result = sim.run();
+        let reports = result.burn_reports();
+        assert_eq!(reports.len(), 1);
+        let burn = reports[0];
+        assert!((burn.start_time - 10.0).abs() < 1e-9);
+        assert!((burn.end_time - 40.0).abs() < 1e-9);
+        // Erwartung aus Schub, Isp und Brenndauer, unabhängig von der Buchführung
+        let flow = sim.config.max_thrust / (sim.config.isp * physics::G0);
+        let final_mass = initial_mass - 30.0 * flow;
+        let expected = sim.config.isp * physics::G0 * (initial_mass / final_mass).ln();
+        let error = (burn.delta_v - expected).abs();
+        assert!(error < 1e-6 * expected, "{} vs {}", burn.delta_v, expected);
+        assert!((burn.propellant - 30.0 * flow).abs() < 1e-6 * burn.propellant);
+    }
+
+    #[test]
+    fn test_patched_conic_matches_full_sim_tli() {
+        let mut sim = MoonMissionSim::new(SimConfig {
+            dt: 1.0,
+            quiet: true,
+            ..Default::default()
+        });
+        let earth = sim.config.constants.earth(Vector3::zeros());
+        let moon = sim.config.constants.moon(Vector3::zeros());
+        let estimate = physics::patched_conic_transfer(
+            earth.radius + sim.config.start_altitude,
+            moon.radius + 100_000.0,
+            earth.mu,
+            moon.mu,
+            physics::MOON_SOI_RADIUS,
+        );
+        let launch_mass = sim.state.mass;
+        let tli_complete = |sim: &MoonMissionSim| {
+            let guidance = sim.guidance.as_any().downcast_ref::<GuidanceComputer>().unwrap();
+            guidance.tli_complete
+        };
+        while !tli_complete(&sim) {
+            assert_eq!(sim.step(), StepOutcome::Running);
+        }
+        sim.step();
+        let tli = sim.burn_reports[0];
+        assert_eq!(tli.phase, MissionPhase::TransLunarInjection);
+
+        // Endlicher Burn mit Gravitationsverlusten: Toleranz 5 %
+        let error = (tli.delta_v - estimate.tli_delta_v).abs() / estimate.tli_delta_v;
+        assert!(error < 0.05, "{:.0} vs {:.0} m/s", tli.delta_v, estimate.tli_delta_v);
+        let exhaust_velocity = sim.config.isp * physics::G0;
+        let propellant = launch_mass * (1.0 - (-estimate.tli_delta_v / exhaust_velocity).exp());
+        let error = (tli.propellant - propellant).abs() / propellant;
+        assert!(error < 0.05, "{:.0} vs {:.0} kg", tli.propellant, propellant);
+    }
+
+    #[test]
+    fn test_escape_detected() {
+        let config = SimConfig {
+            dt: 10.0,
+            max_time: 2.0 * 86_400.0,
+            quiet: true,
+            ..Default::default()
+        };
+        let mut sim = MoonMissionSim::new(config);
+        // 20 % über der Fluchtgeschwindigkeit
+        let r = sim.state.position.norm();
+        let escape_speed = (2.0 * G * M_EARTH / r).sqrt();
+        sim.state.velocity = sim.state.velocity.normalize() * 1.2 * escape_speed;
+
+        let result = sim.run();
+        assert_eq!(result.outcome, StepOutcome::Escaped);
+        assert!(result.mission_time < 86_400.0);
+    }
+
+    #[test]
+    fn test_nominal_transfer_not_escaping() {
+        let mut sim = MoonMissionSim::new(SimConfig {
+            dt: 10.0,
+            quiet: true,
+            ..Default::default()
+        });
+        // Über den TLI-Brennschluss hinaus: Transferbahn bleibt an die Erde gebunden
+        for _ in 0..360 {
+            assert_eq!(sim.step(), StepOutcome::Running, "T+{:.0}s", sim.state.time);
+        }
+        assert_eq!(sim.actual_thrust, Vector3::zeros());
+        assert!(!sim.is_escaping());
+    }
+
+    #[test]
+    fn test_nominal_mission_reaches_loi() {
+        let mut sim = MoonMissionSim::new(SimConfig {
+            dt: 10.0,
+            quiet: true,
+            ..Default::default()
+        });
+        let burn_stopped = |sim: &MoonMissionSim| {
+            sim.timeline.milestones().iter().any(|m| m.kind == MilestoneKind::BurnStop)
+        };
+        while !burn_stopped(&sim) {
+            assert_eq!(sim.step(), StepOutcome::Running, "T+{:.0}s", sim.state.time);
+        }
+
+        // Gebundene Transferbahn nach Brennschluss, Freiflug bis in die LOI-Zone
+        let earth = GravBody::earth(sim.earth_pos);
+        assert!(physics::specific_energy(&sim.state, &earth) < 0.0);
+        let moon = sim.moon_pos;
+        let (distance, time) = physics::closest_approach(&sim.state, |_| moon, 4.0 * 86_400.0);
+        assert!(distance < 66_000_000.0, "Perilunäum {:.0} km", distance / 1_000.0);
+        assert!(distance > physics::R_MOON, "Aufschlag nach {:.0}s", time);
+    }
+
+    #[test]
+    fn test_custom_target_body() {
+        let mars = TargetBody {
+            name: "Mars",
+            radius: 3.3895e6,
+            mu: 4.2828e13,
+            distance_from_earth: 7.8e10,
+            ..Default::default()
+        };
+        let sim = MoonMissionSim::new(SimConfig {
+            target: mars,
+            ..Default::default()
+        });
+
+        assert_eq!(sim.moon_pos.x, mars.distance_from_earth);
+        let guidance = sim.guidance.as_any().downcast_ref::<GuidanceComputer>().unwrap();
+        assert_eq!(guidance.target_position.x, mars.distance_from_earth - mars.radius);
+        assert_eq!(guidance.target_radius, mars.radius);
+        assert_eq!(sim.bodies()[guidance.target_body].name, mars.name);
+        assert_eq!(sim.bodies()[1].mu, mars.mu);
+    }
+
+    #[test]
+    fn test_config_toml_roundtrip() {
+        let config = SimConfig {
+            dt: 2.5,
+            max_time: 7200.0,
+            start_inclination: 0.3,
+            realtime_scale: Some(60.0),
+            seed: 7,
+            ..Default::default()
+        };
+        let loaded = SimConfig::from_toml_str(&config.to_toml_string()).unwrap();
+
+        assert_eq!(loaded.dt, config.dt);
+        assert_eq!(loaded.max_time, config.max_time);
+        assert_eq!(loaded.start_inclination, config.start_inclination);
+        assert_eq!(loaded.realtime_scale, config.realtime_scale);
+        assert_eq!(loaded.seed, config.seed);
+    }
+
+    #[test]
+    fn test_config_toml_roundtrip_target_burns_ephemeris() {
+        let config = SimConfig {
+            target: TargetBody {
+                name: "Mars",
+                radius: 3.3895e6,
+                mu: 4.2828e13,
+                distance_from_earth: 7.8e10,
+                ..Default::default()
+            },
+            scripted_burns: Some(BurnSchedule::new().with_burn(
+                10.0,
+                120.0,
+                Vector3::new(0.0, 5_000.0, 0.0),
+            )),
+            moon_ephemeris: MoonEphemeris::new(vec![
+                (0.0, Vector3::new(physics::EARTH_MOON_DISTANCE, 0.0, 0.0)),
+                (3600.0, Vector3::new(physics::EARTH_MOON_DISTANCE, 3.7e6, 0.0)),
+            ]),
+            ..Default::default()
+        };
+        let loaded = SimConfig::from_toml_str(&config.to_toml_string()).unwrap();
+
+        // Zielkörper über die serialisierten Felder (Geländehöhe ist nicht Teil des Profils)
+        assert_eq!(loaded.target.name, "Mars");
+        assert_eq!(loaded.target.radius, config.target.radius);
+        assert_eq!(loaded.target.mu, config.target.mu);
+        assert_eq!(loaded.target.distance_from_earth, config.target.distance_from_earth);
+        assert_eq!(loaded.scripted_burns, config.scripted_burns);
+        assert_eq!(loaded.moon_ephemeris, config.moon_ephemeris);
+
+        // Leere Ephemeride ist kein gültiges Profil
+        assert!(matches!(
+            SimConfig::from_toml_str("moon_ephemeris = []\n"),
+            Err(ConfigError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_physics_constants_reach_simulation() {
+        let constants = PhysicsConstants {
+            m_earth: 2.0 * M_EARTH,
+            g0: 2.0 * physics::G0,
+            ..Default::default()
+        };
+        let mut sim = MoonMissionSim::new(SimConfig {
+            dt: 1.0,
+            max_time: 10.0,
+            quiet: true,
+            constants,
+            ..Default::default()
+        });
+        let radius = sim.state.position.norm();
+        let expected_speed = (2.0 * G * M_EARTH / radius).sqrt();
+        assert!((sim.state.speed() - expected_speed).abs() < 1e-6);
+        assert_eq!(sim.bodies()[0].mu, 2.0 * G * M_EARTH);
+
+        // Guidance (TLI-Energie, Abbruch-Parkbahn, Phasenlogik) mit derselben Erde
+        let guidance = sim.guidance.as_any().downcast_ref::<GuidanceComputer>().unwrap();
+        assert_eq!(guidance.central_mu, 2.0 * G * M_EARTH);
+        assert_eq!(guidance.central_radius, constants.r_earth);
+
+        // Massenstrom mit dem konfigurierten g0
+        let full = sim.state.velocity.normalize() * sim.config.max_thrust;
+        sim.config.scripted_burns = Some(BurnSchedule::new().with_burn(0.0, 10.0, full));
+        let result = sim.run();
+        let flow = sim.config.max_thrust / (sim.config.isp * 2.0 * physics::G0);
+        assert!((result.fuel_used - 10.0 * flow).abs() < 1e-6, "{} kg", result.fuel_used);
+
+        let loaded = SimConfig::from_toml_str("[constants]\nsoftening = { Plummer = 10.0 }\n");
+        assert_eq!(
+            loaded.unwrap().constants.softening,
+            physics::GravitySoftening::Plummer(10.0)
+        );
+    }
+
+    #[test]
+    fn test_softening_reaches_integration() {
+        let coast = |softening| {
+            let mut sim = MoonMissionSim::new(SimConfig {
+                dt: 1.0,
+                max_time: 10.0,
+                quiet: true,
+                constants: PhysicsConstants {
+                    softening,
+                    ..Default::default()
+                },
+                scripted_burns: Some(BurnSchedule::new()),
+                ..Default::default()
+            });
+            let initial = sim.state.velocity;
+            sim.run();
+            (sim.state.velocity - initial).norm()
+        };
+        // Plummer-Länge in der Größenordnung des Bahnradius schwächt die Erdanziehung
+        let newton = coast(physics::GravitySoftening::DEFAULT);
+        let softened = coast(physics::GravitySoftening::Plummer(R_EARTH));
+        assert!(softened < 0.5 * newton, "{} vs {} m/s", softened, newton);
+    }
+
+    #[test]
+    fn test_config_rejects_unknown_field() {
+        // Tippfehler "max_tme" statt "max_time"
+        assert!(matches!(
+            SimConfig::from_toml_str("dt = 2.0\nmax_tme = 7200.0\n"),
+            Err(ConfigError::Parse(_))
+        ));
+        let target = "[target]\nname = \"Mars\"\nradius = 1.0\nmu = 1.0\n\
+                      distance_from_earth = 1.0\n";
+        assert!(SimConfig::from_toml_str(target).is_ok());
+        assert!(matches!(
+            SimConfig::from_toml_str(&format!("{}radus = 1.0\n", target)),
+            Err(ConfigError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_partial_config_file() {
+        let path = std::env::temp_dir().join("azb_partial_mission.toml");
+        std::fs::write(&path, "dt = 5.0\nquiet = true\n").unwrap();
+        let loaded = SimConfig::from_toml_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let defaults = SimConfig::default();
+        assert_eq!(loaded.dt, 5.0);
+        assert!(loaded.quiet);
+        assert_eq!(loaded.max_time, defaults.max_time);
+        assert_eq!(loaded.isp, defaults.isp);
+        assert_eq!(loaded.seed, defaults.seed);
+
+        assert!(matches!(
+            SimConfig::from_toml_str("dt = \"schnell\""),
+            Err(ConfigError::Parse(_))
+        ));
+        assert!(matches!(
+            SimConfig::from_toml_file(path.with_extension("missing")),
+            Err(ConfigError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_rotating_frame_rejects_forces() {
+        let text = "rotating_frame = true\n[forces]\nj2 = true\n";
+        assert!(matches!(
+            SimConfig::from_toml_str(text),
+            Err(ConfigError::Invalid(_))
+        ));
+        assert!(SimConfig::from_toml_str("rotating_frame = true\n").is_ok());
+        assert!(SimConfig::from_toml_str("[forces]\nj2 = true\n").is_ok());
+    }
+
+    #[test]
+    fn test_rotating_frame_requires_rk4() {
+        let euler = SimConfig {
+            rotating_frame: true,
+            integrator: Integrator::Euler,
+            ..Default::default()
+        };
+        assert!(matches!(euler.validate(), Err(ConfigError::Invalid(_))));
+        let loaded = SimConfig::from_toml_str(&euler.to_toml_string());
+        assert!(matches!(loaded, Err(ConfigError::Invalid(_))));
+        assert!(SimConfig {
+            integrator: Integrator::Euler,
+            ..Default::default()
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_fuel_exhaustion_at_dry_mass() {
+        let config = SimConfig {
+            initial_mass: 15_500.0,
+            dry_mass: 15_000.0,
+            quiet: true,
+            ..Default::default()
+        };
+        let mut sim = MoonMissionSim::new(config);
+        let mut min_mass = f64::INFINITY;
+        let outcome = loop {
+            let outcome = sim.step();
+            min_mass = min_mass.min(sim.state.mass);
+            if outcome != StepOutcome::Running {
+                break outcome;
+            }
+        };
+
+        assert_eq!(outcome, StepOutcome::OutOfFuel);
+        assert_eq!(min_mass, 15_000.0);
+    }
+
+    #[test]
+    fn test_propellant_reserve_inhibits_burns() {
+        let mut sim = MoonMissionSim::new(SimConfig {
+            initial_mass: 15_500.0,
+            dry_mass: 15_000.0,
+            propellant_reserve_fraction: 0.05,
+            quiet: true,
+            ..Default::default()
+        });
+        let outcome = loop {
+            let outcome = sim.step();
+            if outcome != StepOutcome::Running {
+                break outcome;
+            }
+        };
+
+        // TLI-Burn endet an der Reserve (25 kg), nicht an der Trockenmasse; der
+        // nutzbare Treibstoff ist damit erschöpft statt bis `max_time` zu treiben
+        assert_eq!(outcome, StepOutcome::OutOfFuel);
+        assert!(sim.state.time < sim.config.max_time);
+        assert!((sim.state.mass - 15_025.0).abs() < 1e-9, "{} kg", sim.state.mass);
+        let reserve_events = sim.telemetry.get_packets().iter().filter(|p| {
+            matches!(p.payload, TelemetryPayload::Event { event_code: 2011, .. })
+        });
+        assert_eq!(reserve_events.count(), 1);
+
+        // Im Abbruch darf die Reserve verbrannt werden
+        assert!(sim.guidance.abort(&mut NullLogger));
+        for _ in 0..10 {
+            sim.step();
+        }
+        assert!(sim.state.mass < 15_025.0);
+    }
+
+    #[test]
+    fn test_staging_mass_drop_and_isp() {
+        let booster = Stage {
+            dry_mass: 2_000.0,
+            propellant: 1_000.0,
+            isp: 300.0,
+            max_thrust: 50_000.0,
+        };
+        let mut sim = MoonMissionSim::new(SimConfig {
+            dt: 1.0,
+            quiet: true,
+            stages: vec![booster],
+            ..Default::default()
+        });
+        let prograde = sim.state.velocity.normalize();
+        sim.config.scripted_burns =
+            Some(BurnSchedule::new().with_burn(0.0, 1_000.0, prograde * 100_000.0));
+        assert_eq!(sim.state.mass, sim.config.initial_mass + 3_000.0);
+
+        // Unterstufe mit 50 kN Schubgrenze und Isp 300 bis zum Brennschluss
+        let booster_flow = physics::propellant_mass_flow(50_000.0, 300.0);
+        let before = sim.state.mass;
+        sim.step();
+        assert!((before - sim.state.mass - booster_flow).abs() < 1e-6);
+        while sim.active_stage().is_some() {
+            assert_eq!(sim.step(), StepOutcome::Running);
+        }
+
+        // Trennung wirft Struktur ab: genau das Fahrzeug bleibt übrig
+        assert_eq!(sim.state.mass, sim.config.initial_mass);
+        assert!(sim
+            .timeline
+            .milestones()
+            .iter()
+            .any(|m| m.kind == MilestoneKind::StageSeparation));
+
+        // Danach Triebwerk des Fahrzeugs: 100 kN bei Isp 450
+        let before = sim.state.mass;
+        sim.step();
+        let vehicle_flow = physics::propellant_mass_flow(100_000.0, sim.config.isp);
+        assert!((before - sim.state.mass - vehicle_flow).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_guidance_plans_with_active_stage() {
+        let booster = Stage {
+            dry_mass: 2_000.0,
+            propellant: 1_000.0,
+            isp: 300.0,
+            max_thrust: 50_000.0,
+        };
+        let mut sim = MoonMissionSim::new(SimConfig {
+            dt: 1.0,
+            quiet: true,
+            stages: vec![booster],
+            ..Default::default()
+        });
+        let engine = |sim: &MoonMissionSim| {
+            let guidance = sim.guidance.as_any().downcast_ref::<GuidanceComputer>().unwrap();
+            (guidance.max_thrust, guidance.isp)
+        };
+
+        sim.step();
+        assert_eq!(engine(&sim), (50_000.0, 300.0));
+
+        // Nach der Trennung plant die Führung wieder mit dem Fahrzeugtriebwerk
+        sim.stage_index = 1;
+        sim.step();
+        assert_eq!(engine(&sim), (sim.config.max_thrust, sim.config.isp));
+    }
+
+    #[test]
+    fn test_scripted_prograde_burn() {
+        let coast = SimConfig {
+            dt: 1.0,
+            max_time: 100.0,
+            quiet: true,
+            ..Default::default()
+        };
+        let initial_mass = coast.initial_mass;
+        let prograde = MoonMissionSim::new(coast.clone()).state.velocity.normalize();
+        let schedule = BurnSchedule::new().with_burn(10.0, 60.0, prograde * 100_000.0);
+        let burn = SimConfig {
+            scripted_burns: Some(schedule),
+            ..coast.clone()
+        };
+
+        let reference = simulate(&SimConfig {
+            scripted_burns: Some(BurnSchedule::new()),
+            ..coast
+        });
+        let result = simulate(&burn);
+
+        // Raketengleichung: Δv = Isp * g0 * ln(m0 / m1)
+        let final_mass = result.final_state.mass;
+        let expected_dv = burn.isp * physics::G0 * (initial_mass / final_mass).ln();
+        let mass_flow = physics::propellant_mass_flow(100_000.0, burn.isp);
+        assert!((initial_mass - final_mass - mass_flow * 60.0).abs() < 1e-6);
+
+        let dv = (result.final_state.velocity - reference.final_state.velocity).norm();
+        assert!((dv - expected_dv).abs() / expected_dv < 0.01, "Δv {} statt {}", dv, expected_dv);
+    }
+
+    #[test]
+    fn test_timeline_records_tli() {
+        let config = SimConfig {
+            dt: 1.0,
+            max_time: 3600.0,
+            quiet: true,
+            ..Default::default()
+        };
+        let mut sim = MoonMissionSim::new(config);
+        sim.run();
+
+        // Start auf der Parkbahn bereits in der TLI-Phase: Zünden, Brennschluss
+        let milestones = sim.timeline.milestones();
+        let kinds: Vec<MilestoneKind> = milestones.iter().map(|m| m.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                MilestoneKind::Liftoff,
+                MilestoneKind::BurnStart,
+                MilestoneKind::BurnStop,
+                MilestoneKind::MissionEnd,
+            ]
+        );
+        assert_eq!(milestones[1].description, "TransLunarInjection");
+        assert!(milestones[2].time > milestones[1].time);
+        assert!(milestones.windows(2).all(|w| w[0].time <= w[1].time));
+        assert!(sim.export_timeline().contains("TransLunarInjection"));
+    }
+
+    #[test]
+    fn test_sparse_sensor_updates() {
+        let config = SimConfig {
+            dt: 10.0,
+            sensor_interval: 60.0,
+            quiet: true,
+            ..Default::default()
+        };
+        let mut sim = MoonMissionSim::new(config);
+
+        // Erster Schritt misst sofort, danach 5 Schritte nur Predict
+        sim.step();
+        let mut traces = vec![sim.kalman.covariance.trace()];
+        for _ in 0..6 {
+            sim.step();
+            traces.push(sim.kalman.covariance.trace());
+        }
+
+        for pair in traces[..6].windows(2) {
+            assert!(pair[1] > pair[0], "Kovarianz wächst ohne Messung: {:?}", traces);
+        }
+        assert!(traces[6] < traces[5], "Messung verkleinert Kovarianz: {:?}", traces);
+    }
+
+    #[test]
+    fn test_physics_substeps_accuracy() {
+        let config = SimConfig {
+            dt: 60.0,
+            max_time: 180.0,
+            max_thrust: 500_000.0,
+            quiet: true,
+            ..Default::default()
+        };
+        let prograde = MoonMissionSim::new(config.clone()).state.velocity.normalize();
+        let with_substeps = |substeps| {
+            simulate(&SimConfig {
+                physics_substeps: substeps,
+                scripted_burns: Some(
+                    BurnSchedule::new().with_burn(0.0, 150.0, prograde * 500_000.0),
+                ),
+                ..config.clone()
+            })
+            .final_state
+        };
+
+        let reference = with_substeps(240);
+        let coarse = (with_substeps(1).position - reference.position).norm();
+        let fine = (with_substeps(8).position - reference.position).norm();
+        assert!(fine < coarse / 100.0, "Fehler {} (8 Teilschritte) vs {} (1)", fine, coarse);
+    }
+
+    #[test]
+    fn test_integrator_selection() {
+        // Freier Flug in LEO, 20 min bei dt = 10 s: Euler pumpt Energie in die
+        // Bahn (Drift im Bereich kJ/kg), RK4 bleibt um Größenordnungen genauer
+        let config = SimConfig {
+            dt: 10.0,
+            max_time: 1_200.0,
+            quiet: true,
+            scripted_burns: Some(BurnSchedule::new()),
+            ..Default::default()
+        };
+        let sim = MoonMissionSim::new(config.clone());
+        let earth = GravBody::earth(Vector3::zeros());
+        let initial_energy = physics::specific_energy(&sim.state, &earth);
+        let energy_drift = |integrator| {
+            let result = simulate(&SimConfig { integrator, ..config.clone() });
+            physics::specific_energy(&result.final_state, &earth) - initial_energy
+        };
+
+        let euler = energy_drift(Integrator::Euler);
+        let rk4 = energy_drift(Integrator::Rk4);
+        assert!(euler > 1_000.0, "Euler-Drift {} J/kg", euler);
+        assert!(rk4.abs() < euler / 100.0, "RK4-Drift {} vs Euler {} J/kg", rk4, euler);
+    }
+
+    #[test]
+    fn test_gimbal_steered_burn() {
+        let config = SimConfig {
+            dt: 1.0,
+            max_time: 120.0,
+            quiet: true,
+            gimbal_control: true,
+            ..Default::default()
+        };
+        let mut sim = MoonMissionSim::new(config.clone());
+        let prograde = sim.state.velocity.normalize();
+        sim.config.scripted_burns =
+            Some(BurnSchedule::new().with_burn(0.0, 120.0, prograde * 100_000.0));
+        let result = sim.run();
+        let reference = simulate(&SimConfig {
+            scripted_burns: Some(BurnSchedule::new()),
+            ..config
+        });
+
+        // Triebwerksschwenkung dreht die Schubachse in Schubrichtung
+        let axis = sim.attitude.orientation * Vector3::z();
+        assert!(axis.dot(&prograde) > 0.99, "Schubachse {:?}", axis);
+        assert!(sim.gimbal.pitch.abs() <= GIMBAL_MAX_ANGLE);
+        // Während des Schwenkens wirkt der Schub teils quer zur Sollrichtung
+        let dv = result.final_state.velocity - reference.final_state.velocity;
+        assert!(dv.normalize().dot(&prograde) > 0.9);
+    }
+
+    #[test]
+    fn test_thrust_direction_change_starts_slew() {
+        let mut sim = MoonMissionSim::new(SimConfig {
+            dt: 1.0,
+            quiet: true,
+            ..Default::default()
+        });
+        let prograde = sim.state.velocity.normalize();
+        sim.config.scripted_burns =
+            Some(BurnSchedule::new().with_burn(0.0, 100.0, prograde * 100_000.0));
+
+        // Neue Schubrichtung 90° neben der Schubachse: Schwenk über ~31 s
+        sim.step();
+        let slew = sim.attitude.slew.clone().expect("Schwenk gestartet");
+        let expected = std::f64::consts::FRAC_PI_2 / ATTITUDE_SLEW_RATE;
+        assert!((slew.duration - expected).abs() < 1e-6, "{} s", slew.duration);
+        // Gleiches Kommando setzt den Schwenk fort statt ihn neu zu beginnen
+        for _ in 0..10 {
+            sim.step();
+        }
+        let running = sim.attitude.slew.as_ref().expect("Schwenk läuft");
+        assert_eq!(running.from, slew.from);
+        assert!((running.elapsed - 11.0).abs() < 1e-6, "{} s", running.elapsed);
+    }
+
+    #[test]
+    fn test_rcs_propellant_from_vehicle_mass() {
+        let config = SimConfig {
+            dt: 1.0,
+            max_time: 30.0,
+            quiet: true,
+            rcs_control: true,
+            initial_attitude: Some(InitialAttitude {
+                euler: [0.4, -0.3, 1.2],
+                angular_velocity: [0.2, -0.1, 0.15],
+            }),
+            scripted_burns: Some(BurnSchedule::new()),
+            ..Default::default()
+        };
+        let mut sim = MoonMissionSim::new(config.clone());
+        let result = sim.run();
+
+        // Entdrallen ohne Haupttriebwerk: Verbrauch allein durch die Düsen
+        assert!(sim.attitude.angular_velocity.norm() < 1e-2, "{:?}", sim.attitude);
+        assert!(result.fuel_used > 0.0);
+        assert!(result.final_state.mass < config.launch_mass());
+        // Grobe Obergrenze: alle zwölf Düsen über die ganze Laufzeit
+        let all_jets = 12.0 * physics::propellant_mass_flow(RCS_JET_THRUST, sim.rcs.isp) * 30.0;
+        assert!(result.fuel_used < all_jets, "{} kg", result.fuel_used);
+    }
+
+    #[test]
+    fn test_heavier_vehicle_slews_slower() {
+        let slew_error = |initial_mass: f64| {
+            let mut sim = MoonMissionSim::new(SimConfig {
+                initial_mass,
+                quiet: true,
+                scripted_burns: Some(BurnSchedule::new()),
+                ..Default::default()
+            });
+            sim.attitude.point_towards(&Vector3::x());
+            for _ in 0..3 {
+                sim.step();
+            }
+            sim.attitude.orientation.angle_to(&sim.attitude.target_orientation)
+        };
+
+        // Gleiches Stellmoment, größere Trägheit: weniger Winkel in gleicher Zeit
+        let light = slew_error(15_000.0);
+        let heavy = slew_error(45_000.0);
+        assert!(light < heavy, "{} rad vs {} rad", light, heavy);
+    }
+
+    #[test]
+    fn test_inertia_decreases_with_fuel() {
+        let sim = MoonMissionSim::new(SimConfig::default());
+        let wet = sim.config.initial_mass;
+        let dry = sim.config.dry_mass;
+
+        let mut previous = sim.inertia_for_mass(wet);
+        for i in 1..=10 {
+            let mass = wet - (wet - dry) * i as f64 / 10.0;
+            let inertia = sim.inertia_for_mass(mass);
+            for axis in 0..3 {
+                assert!(inertia[(axis, axis)] < previous[(axis, axis)]);
+            }
+            previous = inertia;
+        }
+        assert_eq!(previous, physics::vehicle_inertia(dry, dry));
     }
 }
@@ -3,15 +3,44 @@
 //! Implementiert:
 //! - Telemetrie-Pakete mit CRC
 //! - Event-Logging
-//! - Daten-Serialisierung
+//! - Daten-Serialisierung (Binär und JSON)
 
+use crate::physics::{SpacecraftState, R_EARTH, R_MOON};
+use nalgebra::Vector3;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// CRC-64 Polynom (vereinfacht)
 const CRC_POLY: u64 = 0x42F0E1EBA9EA3693;
 
+/// Version des Binärformats (`to_bytes`, `compress_navigation`), erhöht bei
+/// jeder Änderung des Paketlayouts:
+/// - 1: Status mit verbleibendem Δv
+/// - 2: Diagnostics mit Ausrichtungsfehler des Schubs
+/// - 3: Diagnostics mit Positionsunsicherheit der Navigation
+/// - 4: Navigation mit Bezugssystem
+/// - 5: Zustand des Zielkörpers (Target)
+pub const TELEMETRY_FORMAT_VERSION: u8 = 5;
+
+/// Länge des CCSDS Space Packet Primary Headers [Bytes]
+pub const CCSDS_PRIMARY_HEADER_LEN: usize = 6;
+
+/// Delta-Kompression: vollständiger Navigationsdatensatz (f64)
+const NAV_KEYFRAME: u8 = 0x00;
+/// Delta-Kompression: Differenz zum Vorgänger (f32)
+const NAV_DELTA: u8 = 0x01;
+/// Größte Komponentendifferenz, die noch als f32-Delta kodiert wird
+/// (Rundungsfehler ≲ 0.1 m bzw. m/s)
+const NAV_MAX_DELTA: f64 = 1.0e6;
+
 /// Telemetrie-Paket
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct TelemetryPacket {
     /// Zeitstempel (Unix-Zeit in ms)
     pub timestamp: u64,
@@ -21,11 +50,13 @@ pub struct TelemetryPacket {
     pub subsystem: SubsystemId,
     /// Nutzdaten
     pub payload: TelemetryPayload,
+    /// Downlink-Priorität (höher = früher), aus dem Payload abgeleitet
+    pub priority: u8,
     /// CRC-64 Prüfsumme
     pub crc: u64,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum SubsystemId {
     GNC = 1,
     FDIR = 2,
@@ -35,18 +66,121 @@ pub enum SubsystemId {
     Communication = 6,
 }
 
-#[derive(Debug, Clone)]
+impl SubsystemId {
+    /// Dekodiert Subsystem-ID aus Rohwert
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(SubsystemId::GNC),
+            2 => Some(SubsystemId::FDIR),
+            3 => Some(SubsystemId::Propulsion),
+            4 => Some(SubsystemId::Thermal),
+            5 => Some(SubsystemId::Power),
+            6 => Some(SubsystemId::Communication),
+            _ => None,
+        }
+    }
+}
+
+/// Schweregrad eines Ereignisses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum EventSeverity {
+    Info = 0,
+    Warning = 1,
+    Error = 2,
+    Critical = 3,
+}
+
+impl EventSeverity {
+    /// Dekodiert Schweregrad aus Rohwert
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(EventSeverity::Info),
+            1 => Some(EventSeverity::Warning),
+            2 => Some(EventSeverity::Error),
+            3 => Some(EventSeverity::Critical),
+            _ => None,
+        }
+    }
+}
+
+/// Bezugssystem der Navigationsdaten
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ReferenceFrame {
+    /// Erdzentriert, inertial (ECI)
+    #[default]
+    EarthCentered = 0,
+    /// Mondzentriert, inertial (MCI), relativ zu `moon_pos`
+    MoonCentered = 1,
+}
+
+impl ReferenceFrame {
+    /// Dekodiert Bezugssystem aus Rohwert
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(ReferenceFrame::EarthCentered),
+            1 => Some(ReferenceFrame::MoonCentered),
+            _ => None,
+        }
+    }
+
+    /// Kurzbezeichnung für Textausgaben
+    pub fn label(self) -> &'static str {
+        match self {
+            ReferenceFrame::EarthCentered => "ECI",
+            ReferenceFrame::MoonCentered => "MCI",
+        }
+    }
+
+    /// Rechnet einen erdzentrierten Zustand in dieses System um
+    /// (`moon_pos`, `moon_velocity` erdzentriert)
+    pub fn from_earth_centered(
+        self,
+        position: &Vector3<f64>,
+        velocity: &Vector3<f64>,
+        moon_pos: &Vector3<f64>,
+        moon_velocity: &Vector3<f64>,
+    ) -> ([f64; 3], [f64; 3]) {
+        let (position, velocity) = match self {
+            ReferenceFrame::EarthCentered => (*position, *velocity),
+            ReferenceFrame::MoonCentered => (position - moon_pos, velocity - moon_velocity),
+        };
+        (position.into(), velocity.into())
+    }
+
+    /// Rechnet einen Zustand aus diesem System zurück ins erdzentrierte System
+    /// (Umkehrung von `from_earth_centered`)
+    pub fn to_earth_centered(
+        self,
+        position: &[f64; 3],
+        velocity: &[f64; 3],
+        moon_pos: &Vector3<f64>,
+        moon_velocity: &Vector3<f64>,
+    ) -> (Vector3<f64>, Vector3<f64>) {
+        let (position, velocity) = (Vector3::from(*position), Vector3::from(*velocity));
+        match self {
+            ReferenceFrame::EarthCentered => (position, velocity),
+            ReferenceFrame::MoonCentered => (position + moon_pos, velocity + moon_velocity),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
 pub enum TelemetryPayload {
     /// Position und Geschwindigkeit
     Navigation {
         position: [f64; 3],
         velocity: [f64; 3],
+        /// Bezugssystem von Position und Geschwindigkeit
+        frame: ReferenceFrame,
     },
     /// Systemstatus
     Status {
         phase: u8,
         fuel_percent: f32,
         system_health: u8,
+        /// Verbleibendes Δv [m/s]
+        delta_v_remaining: f32,
     },
     /// Sensorwerte
     Sensors {
@@ -57,8 +191,45 @@ pub enum TelemetryPayload {
     /// Ereignis
     Event {
         event_code: u16,
+        severity: EventSeverity,
         message: String,
     },
+    /// Integrator- und Regler-Diagnose
+    Diagnostics {
+        /// Spezifische mechanische Gesamtenergie [J/kg]
+        specific_energy: f64,
+        /// Winkel zwischen kommandierter Schubrichtung und Schubachse [rad]
+        pointing_error: f32,
+        /// Positionsunsicherheit des Navigationsfilters (1σ) [m]
+        position_uncertainty: f32,
+    },
+    /// Fahrzeuglage
+    Attitude {
+        /// Lage-Quaternion (w, x, y, z) Körper- → Inertialsystem
+        quaternion: [f64; 4],
+        /// Drehrate im Körpersystem [rad/s]
+        angular_velocity: [f64; 3],
+    },
+    /// Erdzentrierter Zustand des Zielkörpers (Bezug mondzentrierter Navigation)
+    Target {
+        position: [f64; 3],
+        velocity: [f64; 3],
+    },
+}
+
+impl TelemetryPayload {
+    /// Downlink-Priorität: Ereignisse (nach Schweregrad) > Status > Navigation/Messwerte
+    pub fn priority(&self) -> u8 {
+        match self {
+            TelemetryPayload::Navigation { .. }
+            | TelemetryPayload::Sensors { .. }
+            | TelemetryPayload::Diagnostics { .. }
+            | TelemetryPayload::Attitude { .. }
+            | TelemetryPayload::Target { .. } => 0,
+            TelemetryPayload::Status { .. } => 1,
+            TelemetryPayload::Event { severity, .. } => 2 + *severity as u8,
+        }
+    }
 }
 
 impl TelemetryPacket {
@@ -67,11 +238,21 @@ impl TelemetryPacket {
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_millis() as u64;
+        Self::with_timestamp(packet_id, subsystem, payload, timestamp)
+    }
 
+    /// Erstellt Paket mit vorgegebenem Zeitstempel [ms] (z.B. Missionszeit)
+    pub fn with_timestamp(
+        packet_id: u32,
+        subsystem: SubsystemId,
+        payload: TelemetryPayload,
+        timestamp: u64,
+    ) -> Self {
         let mut packet = Self {
             timestamp,
             packet_id,
             subsystem,
+            priority: payload.priority(),
             payload,
             crc: 0,
         };
@@ -117,18 +298,26 @@ impl TelemetryPacket {
     }
 
     /// Serialisiert zu Bytes (vereinfachte Binär-Serialisierung)
+    ///
+    /// Der Header beginnt mit `TELEMETRY_FORMAT_VERSION`.
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut bytes = Vec::new();
 
         // Header
+        bytes.push(TELEMETRY_FORMAT_VERSION);
         bytes.extend_from_slice(&self.timestamp.to_le_bytes());
         bytes.extend_from_slice(&self.packet_id.to_le_bytes());
         bytes.push(self.subsystem as u8);
 
         // Payload-Typ + Daten
         match &self.payload {
-            TelemetryPayload::Navigation { position, velocity } => {
+            TelemetryPayload::Navigation {
+                position,
+                velocity,
+                frame,
+            } => {
                 bytes.push(0x01);
+                bytes.push(*frame as u8);
                 for v in position {
                     bytes.extend_from_slice(&v.to_le_bytes());
                 }
@@ -140,11 +329,13 @@ impl TelemetryPacket {
                 phase,
                 fuel_percent,
                 system_health,
+                delta_v_remaining,
             } => {
                 bytes.push(0x02);
                 bytes.push(*phase);
                 bytes.extend_from_slice(&fuel_percent.to_le_bytes());
                 bytes.push(*system_health);
+                bytes.extend_from_slice(&delta_v_remaining.to_le_bytes());
             }
             TelemetryPayload::Sensors {
                 temperature,
@@ -158,14 +349,41 @@ impl TelemetryPacket {
             }
             TelemetryPayload::Event {
                 event_code,
+                severity,
                 message,
             } => {
                 bytes.push(0x04);
                 bytes.extend_from_slice(&event_code.to_le_bytes());
+                bytes.push(*severity as u8);
                 let msg_bytes = message.as_bytes();
                 bytes.extend_from_slice(&(msg_bytes.len() as u16).to_le_bytes());
                 bytes.extend_from_slice(msg_bytes);
             }
+            TelemetryPayload::Diagnostics {
+                specific_energy,
+                pointing_error,
+                position_uncertainty,
+            } => {
+                bytes.push(0x05);
+                bytes.extend_from_slice(&specific_energy.to_le_bytes());
+                bytes.extend_from_slice(&pointing_error.to_le_bytes());
+                bytes.extend_from_slice(&position_uncertainty.to_le_bytes());
+            }
+            TelemetryPayload::Attitude {
+                quaternion,
+                angular_velocity,
+            } => {
+                bytes.push(0x06);
+                for v in quaternion.iter().chain(angular_velocity) {
+                    bytes.extend_from_slice(&v.to_le_bytes());
+                }
+            }
+            TelemetryPayload::Target { position, velocity } => {
+                bytes.push(0x07);
+                for v in position.iter().chain(velocity) {
+                    bytes.extend_from_slice(&v.to_le_bytes());
+                }
+            }
         }
 
         // CRC am Ende
@@ -173,12 +391,204 @@ impl TelemetryPacket {
 
         bytes
     }
+
+    /// Deserialisiert Paket aus Bytes (Gegenstück zu `to_bytes`)
+    ///
+    /// Pakete einer anderen Formatversion werden abgelehnt.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = ByteReader::new(bytes);
+
+        // Header
+        if reader.u8()? != TELEMETRY_FORMAT_VERSION {
+            return None;
+        }
+        let timestamp = u64::from_le_bytes(reader.take()?);
+        let packet_id = u32::from_le_bytes(reader.take()?);
+        let subsystem = SubsystemId::from_u8(reader.u8()?)?;
+
+        // Payload-Typ + Daten
+        let payload = match reader.u8()? {
+            0x01 => {
+                let frame = ReferenceFrame::from_u8(reader.u8()?)?;
+                let mut position = [0.0; 3];
+                let mut velocity = [0.0; 3];
+                for v in position.iter_mut().chain(velocity.iter_mut()) {
+                    *v = f64::from_le_bytes(reader.take()?);
+                }
+                TelemetryPayload::Navigation {
+                    position,
+                    velocity,
+                    frame,
+                }
+            }
+            0x02 => TelemetryPayload::Status {
+                phase: reader.u8()?,
+                fuel_percent: f32::from_le_bytes(reader.take()?),
+                system_health: reader.u8()?,
+                delta_v_remaining: f32::from_le_bytes(reader.take()?),
+            },
+            0x03 => TelemetryPayload::Sensors {
+                temperature: f32::from_le_bytes(reader.take()?),
+                pressure: f32::from_le_bytes(reader.take()?),
+                radiation: f32::from_le_bytes(reader.take()?),
+            },
+            0x04 => {
+                let event_code = u16::from_le_bytes(reader.take()?);
+                let severity = EventSeverity::from_u8(reader.u8()?)?;
+                let len = u16::from_le_bytes(reader.take()?) as usize;
+                let message = String::from_utf8(reader.slice(len)?.to_vec()).ok()?;
+                TelemetryPayload::Event {
+                    event_code,
+                    severity,
+                    message,
+                }
+            }
+            0x05 => TelemetryPayload::Diagnostics {
+                specific_energy: f64::from_le_bytes(reader.take()?),
+                pointing_error: f32::from_le_bytes(reader.take()?),
+                position_uncertainty: f32::from_le_bytes(reader.take()?),
+            },
+            0x06 => {
+                let mut quaternion = [0.0; 4];
+                let mut angular_velocity = [0.0; 3];
+                for v in quaternion.iter_mut().chain(angular_velocity.iter_mut()) {
+                    *v = f64::from_le_bytes(reader.take()?);
+                }
+                TelemetryPayload::Attitude {
+                    quaternion,
+                    angular_velocity,
+                }
+            }
+            0x07 => {
+                let mut position = [0.0; 3];
+                let mut velocity = [0.0; 3];
+                for v in position.iter_mut().chain(velocity.iter_mut()) {
+                    *v = f64::from_le_bytes(reader.take()?);
+                }
+                TelemetryPayload::Target { position, velocity }
+            }
+            _ => return None,
+        };
+
+        let crc = u64::from_le_bytes(reader.take()?);
+
+        Some(Self {
+            timestamp,
+            packet_id,
+            subsystem,
+            priority: payload.priority(),
+            payload,
+            crc,
+        })
+    }
+
+    /// Kodiert Paket als CCSDS Space Packet
+    ///
+    /// Primary Header (6 Bytes, Big-Endian):
+    /// - Version (3 Bit) = 0, Typ (1 Bit) = 0 (Telemetrie), Sec. Header Flag (1 Bit) = 0
+    /// - APID (11 Bit) = Subsystem-ID
+    /// - Sequence Flags (2 Bit) = 0b11 (unsegmentiert), Sequence Count (14 Bit) = Paket-ID
+    /// - Data Length (16 Bit) = Länge des Datenfelds - 1
+    ///
+    /// Das Datenfeld enthält die Serialisierung aus `to_bytes`.
+    pub fn to_ccsds(&self) -> Vec<u8> {
+        let data = self.to_bytes();
+
+        let apid = (self.subsystem as u16) & 0x07FF;
+        let sequence = (0b11 << 14) | (self.packet_id as u16 & 0x3FFF);
+        let data_length = (data.len() - 1) as u16;
+
+        let mut bytes = Vec::with_capacity(CCSDS_PRIMARY_HEADER_LEN + data.len());
+        bytes.extend_from_slice(&apid.to_be_bytes());
+        bytes.extend_from_slice(&sequence.to_be_bytes());
+        bytes.extend_from_slice(&data_length.to_be_bytes());
+        bytes.extend_from_slice(&data);
+        bytes
+    }
+
+    /// Dekodiert CCSDS Space Packet (Gegenstück zu `to_ccsds`)
+    pub fn from_ccsds(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < CCSDS_PRIMARY_HEADER_LEN {
+            return None;
+        }
+
+        let word0 = u16::from_be_bytes([bytes[0], bytes[1]]);
+        let data_length = u16::from_be_bytes([bytes[4], bytes[5]]) as usize + 1;
+
+        // Nur Version 0 unterstützt
+        if word0 >> 13 != 0 {
+            return None;
+        }
+
+        let data = bytes.get(CCSDS_PRIMARY_HEADER_LEN..CCSDS_PRIMARY_HEADER_LEN + data_length)?;
+        let packet = Self::from_bytes(data)?;
+
+        // APID muss zum Subsystem im Datenfeld passen
+        if word0 & 0x07FF != packet.subsystem as u16 {
+            return None;
+        }
+
+        Some(packet)
+    }
+}
+
+/// Einfacher Lese-Cursor für die Binär-Deserialisierung
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn slice(&mut self, len: usize) -> Option<&'a [u8]> {
+        let slice = self.bytes.get(self.pos..self.pos + len)?;
+        self.pos += len;
+        Some(slice)
+    }
+
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.slice(N)?.try_into().ok()
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take::<1>()?[0])
+    }
+}
+
+/// Aus der Telemetrie abgeleitete Missionskennzahlen
+///
+/// Erde im Ursprung; die Mondposition stammt aus dem jeweils zuletzt
+/// geloggten Zielkörper-Zustand (`TelemetryPayload::Target`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct MissionStats {
+    /// Maximale Geschwindigkeit [m/s]
+    pub max_speed: f64,
+    /// Maximale Höhe über der Erdoberfläche [m]
+    pub max_earth_altitude: f64,
+    /// Minimale Höhe über der Mondoberfläche [m] (∞ ohne Navigationsdaten
+    /// mit bekannter Mondposition)
+    pub min_lunar_altitude: f64,
+    /// Zurückgelegte Strecke zwischen den Navigationspaketen [m]
+    pub total_distance: f64,
+    /// Minimaler Treibstoffstand [%] (100 ohne Statuspakete)
+    pub min_fuel_percent: f32,
 }
 
 /// Telemetrie-Logger
 pub struct TelemetryLogger {
     packets: Vec<TelemetryPacket>,
     next_id: u32,
+    /// Maximale Anzahl gehaltener Pakete (None = unbegrenzt)
+    capacity: Option<usize>,
+    /// Anzahl verworfener (ältester) Pakete im Ringpuffer-Modus
+    dropped: usize,
+    /// Missionszeit für Zeitstempel [ms] (None = Systemzeit)
+    mission_time_ms: Option<u64>,
+    /// Missionsepoche, auf die die Missionszeit addiert wird [ms]
+    epoch_ms: u64,
 }
 
 impl TelemetryLogger {
@@ -186,38 +596,158 @@ impl TelemetryLogger {
         Self {
             packets: Vec::new(),
             next_id: 1,
+            capacity: None,
+            dropped: 0,
+            mission_time_ms: None,
+            epoch_ms: 0,
         }
     }
 
-    /// Loggt Navigationsdaten
+    /// Ringpuffer-Modus: behält nur die jüngsten `capacity` Pakete
+    /// (für Langzeitmissionen auf Embedded-Zielen)
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            packets: Vec::with_capacity(capacity),
+            next_id: 1,
+            capacity: Some(capacity),
+            dropped: 0,
+            mission_time_ms: None,
+            epoch_ms: 0,
+        }
+    }
+
+    /// Zeitstempel relativ zu einer Missionsepoche (z.B. Startzeit, Unix-ms):
+    /// `epoch_ms + Missionszeit * 1000`, ab sofort mit Missionszeit 0
+    pub fn with_epoch(mut self, epoch_ms: u64) -> Self {
+        self.epoch_ms = epoch_ms;
+        self.mission_time_ms = Some(epoch_ms);
+        self
+    }
+
+    /// Stempelt folgende Pakete mit der Missionszeit statt der Systemzeit
+    /// (reproduzierbare Telemetrie)
+    pub fn set_mission_time(&mut self, time: f64) {
+        self.mission_time_ms = Some(self.epoch_ms + (time * 1000.0).round() as u64);
+    }
+
+    /// Loggt Navigationsdaten (erdzentriert)
     pub fn log_navigation(&mut self, position: [f64; 3], velocity: [f64; 3]) {
-        let payload = TelemetryPayload::Navigation { position, velocity };
+        self.log_navigation_in(ReferenceFrame::EarthCentered, position, velocity);
+    }
+
+    /// Loggt Navigationsdaten im Bezugssystem `frame`
+    /// (Umrechnung siehe `ReferenceFrame::from_earth_centered`)
+    pub fn log_navigation_in(
+        &mut self,
+        frame: ReferenceFrame,
+        position: [f64; 3],
+        velocity: [f64; 3],
+    ) {
+        let payload = TelemetryPayload::Navigation {
+            position,
+            velocity,
+            frame,
+        };
         self.log(SubsystemId::GNC, payload);
     }
 
     /// Loggt Systemstatus
-    pub fn log_status(&mut self, phase: u8, fuel_percent: f32, system_health: u8) {
+    pub fn log_status(
+        &mut self,
+        phase: u8,
+        fuel_percent: f32,
+        system_health: u8,
+        delta_v_remaining: f32,
+    ) {
         let payload = TelemetryPayload::Status {
             phase,
             fuel_percent,
             system_health,
+            delta_v_remaining,
         };
         self.log(SubsystemId::FDIR, payload);
     }
 
+    /// Loggt Umgebungssensoren
+    pub fn log_sensors(&mut self, temperature: f32, pressure: f32, radiation: f32) {
+        let payload = TelemetryPayload::Sensors {
+            temperature,
+            pressure,
+            radiation,
+        };
+        self.log(SubsystemId::Thermal, payload);
+    }
+
+    /// Loggt Diagnose (spezifische Gesamtenergie, Schub-Ausrichtungsfehler [rad],
+    /// Positionsunsicherheit der Navigation [m])
+    pub fn log_diagnostics(
+        &mut self,
+        specific_energy: f64,
+        pointing_error: f32,
+        position_uncertainty: f32,
+    ) {
+        let payload = TelemetryPayload::Diagnostics {
+            specific_energy,
+            pointing_error,
+            position_uncertainty,
+        };
+        self.log(SubsystemId::GNC, payload);
+    }
+
+    /// Loggt Fahrzeuglage (Quaternion w, x, y, z und Drehrate [rad/s])
+    pub fn log_attitude(&mut self, quaternion: [f64; 4], angular_velocity: [f64; 3]) {
+        let payload = TelemetryPayload::Attitude {
+            quaternion,
+            angular_velocity,
+        };
+        self.log(SubsystemId::GNC, payload);
+    }
+
+    /// Loggt den erdzentrierten Zustand des Zielkörpers
+    pub fn log_target(&mut self, position: [f64; 3], velocity: [f64; 3]) {
+        let payload = TelemetryPayload::Target { position, velocity };
+        self.log(SubsystemId::GNC, payload);
+    }
+
     /// Loggt Ereignis
-    pub fn log_event(&mut self, subsystem: SubsystemId, event_code: u16, message: &str) {
+    pub fn log_event(
+        &mut self,
+        subsystem: SubsystemId,
+        event_code: u16,
+        severity: EventSeverity,
+        message: &str,
+    ) {
         let payload = TelemetryPayload::Event {
             event_code,
+            severity,
             message: message.to_string(),
         };
         self.log(subsystem, payload);
     }
 
     fn log(&mut self, subsystem: SubsystemId, payload: TelemetryPayload) {
-        let packet = TelemetryPacket::new(self.next_id, subsystem, payload);
+        let packet = match self.mission_time_ms {
+            Some(timestamp) => {
+                TelemetryPacket::with_timestamp(self.next_id, subsystem, payload, timestamp)
+            }
+            None => TelemetryPacket::new(self.next_id, subsystem, payload),
+        };
         self.packets.push(packet);
         self.next_id += 1;
+
+        // Ältestes Paket verwerfen wenn Ringpuffer voll
+        if let Some(capacity) = self.capacity {
+            if self.packets.len() > capacity {
+                let excess = self.packets.len() - capacity;
+                self.packets.drain(..excess);
+                self.dropped += excess;
+            }
+        }
+    }
+
+    /// Anzahl der im Ringpuffer-Modus verworfenen Pakete
+    pub fn dropped_count(&self) -> usize {
+        self.dropped
     }
 
     /// Gibt alle Pakete zurück
@@ -225,6 +755,147 @@ impl TelemetryLogger {
         &self.packets
     }
 
+    /// Gibt alle Pakete eines Subsystems zurück
+    pub fn packets_for(&self, subsystem: SubsystemId) -> Vec<&TelemetryPacket> {
+        self.packets
+            .iter()
+            .filter(|p| p.subsystem == subsystem)
+            .collect()
+    }
+
+    /// Gibt alle Pakete im Zeitfenster [start_ms, end_ms] zurück
+    pub fn packets_between(&self, start_ms: u64, end_ms: u64) -> Vec<&TelemetryPacket> {
+        self.packets
+            .iter()
+            .filter(|p| p.timestamp >= start_ms && p.timestamp <= end_ms)
+            .collect()
+    }
+
+    /// Entnimmt Pakete für einen Downlink-Slot mit begrenzter Kapazität
+    ///
+    /// Höchste Priorität zuerst, innerhalb einer Priorität in Log-Reihenfolge.
+    /// Stoppt beim ersten Paket, das nicht mehr in `budget_bytes` passt;
+    /// nicht übertragene Pakete bleiben im Logger.
+    pub fn drain_by_priority(&mut self, budget_bytes: usize) -> Vec<TelemetryPacket> {
+        let mut order: Vec<usize> = (0..self.packets.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(self.packets[i].priority));
+
+        let mut used = 0;
+        let mut selected = Vec::new();
+        let mut is_selected = vec![false; self.packets.len()];
+        for i in order {
+            let size = self.packets[i].to_bytes().len();
+            if used + size > budget_bytes {
+                break;
+            }
+            used += size;
+            selected.push(i);
+            is_selected[i] = true;
+        }
+
+        let drained = selected.iter().map(|&i| self.packets[i].clone()).collect();
+        // Markierung statt Suche: linear auch bei sehr langen Logs
+        let mut marks = is_selected.into_iter();
+        self.packets.retain(|_| !marks.next().unwrap_or(false));
+        drained
+    }
+
+    /// Spielt alle aufgezeichneten Pakete in Reihenfolge durch `f` ab
+    /// (Auswertung nach der Mission ohne erneute Physik-Simulation)
+    pub fn replay<F: FnMut(&TelemetryPacket)>(&self, f: F) {
+        self.packets.iter().for_each(f);
+    }
+
+    /// Rekonstruiert die Trajektorie allein aus den Navigationspaketen
+    ///
+    /// Zeit aus dem Zeitstempel [s] (relativ zur Epoche); die Masse wird nicht
+    /// übertragen und ist 0. Alle Zustände erdzentriert: mondzentrierte Pakete
+    /// werden mit dem zuletzt geloggten Zielkörper-Zustand umgerechnet und ohne
+    /// einen solchen ausgelassen.
+    pub fn replay_trajectory(&self) -> Vec<SpacecraftState> {
+        let mut trajectory = Vec::new();
+        let mut target: Option<(Vector3<f64>, Vector3<f64>)> = None;
+        self.replay(|packet| match &packet.payload {
+            TelemetryPayload::Target { position, velocity } => {
+                target = Some((Vector3::from(*position), Vector3::from(*velocity)));
+            }
+            TelemetryPayload::Navigation {
+                position,
+                velocity,
+                frame,
+            } => {
+                let (moon_pos, moon_velocity) = match (frame, target) {
+                    (ReferenceFrame::EarthCentered, _) => (Vector3::zeros(), Vector3::zeros()),
+                    (ReferenceFrame::MoonCentered, Some(target)) => target,
+                    (ReferenceFrame::MoonCentered, None) => return,
+                };
+                let (position, velocity) =
+                    frame.to_earth_centered(position, velocity, &moon_pos, &moon_velocity);
+                let mut state = SpacecraftState::new(position, velocity, 0.0);
+                state.time = packet.timestamp.saturating_sub(self.epoch_ms) as f64 / 1000.0;
+                trajectory.push(state);
+            }
+            _ => {}
+        });
+        trajectory
+    }
+
+    /// Berechnet Missionskennzahlen aus Navigations- und Statuspaketen
+    pub fn statistics(&self) -> MissionStats {
+        let mut moon: Option<Vector3<f64>> = None;
+        let mut stats = MissionStats {
+            max_speed: 0.0,
+            max_earth_altitude: 0.0,
+            min_lunar_altitude: f64::INFINITY,
+            total_distance: 0.0,
+            min_fuel_percent: 100.0,
+        };
+        let mut last_position: Option<(ReferenceFrame, Vector3<f64>)> = None;
+
+        for packet in &self.packets {
+            match &packet.payload {
+                TelemetryPayload::Navigation {
+                    position,
+                    velocity,
+                    frame,
+                } => {
+                    let position = Vector3::from(*position);
+                    stats.max_speed = stats.max_speed.max(Vector3::from(*velocity).norm());
+                    let (earth_distance, lunar_distance) = match frame {
+                        ReferenceFrame::EarthCentered => {
+                            (Some(position.norm()), moon.map(|m| (position - m).norm()))
+                        }
+                        ReferenceFrame::MoonCentered => {
+                            (moon.map(|m| (position + m).norm()), Some(position.norm()))
+                        }
+                    };
+                    if let Some(distance) = earth_distance {
+                        stats.max_earth_altitude = stats.max_earth_altitude.max(distance - R_EARTH);
+                    }
+                    if let Some(distance) = lunar_distance {
+                        stats.min_lunar_altitude = stats.min_lunar_altitude.min(distance - R_MOON);
+                    }
+                    // Strecke nur innerhalb desselben Bezugssystems
+                    if let Some((last_frame, last)) = last_position {
+                        if last_frame == *frame {
+                            stats.total_distance += (position - last).norm();
+                        }
+                    }
+                    last_position = Some((*frame, position));
+                }
+                TelemetryPayload::Status { fuel_percent, .. } => {
+                    stats.min_fuel_percent = stats.min_fuel_percent.min(*fuel_percent);
+                }
+                TelemetryPayload::Target { position, .. } => {
+                    moon = Some(Vector3::from(*position));
+                }
+                _ => {}
+            }
+        }
+
+        stats
+    }
+
     /// Exportiert Telemetrie als Text
     pub fn export_summary(&self) -> String {
         let mut output = String::new();
@@ -237,9 +908,14 @@ impl TelemetryLogger {
                 packet.timestamp, packet.packet_id, packet.subsystem
             ));
             match &packet.payload {
-                TelemetryPayload::Navigation { position, velocity } => {
+                TelemetryPayload::Navigation {
+                    position,
+                    velocity,
+                    frame,
+                } => {
                     output.push_str(&format!(
-                        "NAV pos=[{:.0}, {:.0}, {:.0}]m vel=[{:.1}, {:.1}, {:.1}]m/s\n",
+                        "NAV {} pos=[{:.0}, {:.0}, {:.0}]m vel=[{:.1}, {:.1}, {:.1}]m/s\n",
+                        frame.label(),
                         position[0],
                         position[1],
                         position[2],
@@ -252,10 +928,11 @@ impl TelemetryLogger {
                     phase,
                     fuel_percent,
                     system_health,
+                    delta_v_remaining,
                 } => {
                     output.push_str(&format!(
-                        "STATUS phase={} fuel={:.1}% health={}\n",
-                        phase, fuel_percent, system_health
+                        "STATUS phase={} fuel={:.1}% health={} dv={:.0}m/s\n",
+                        phase, fuel_percent, system_health, delta_v_remaining
                     ));
                 }
                 TelemetryPayload::Sensors {
@@ -270,15 +947,208 @@ impl TelemetryLogger {
                 }
                 TelemetryPayload::Event {
                     event_code,
+                    severity,
                     message,
                 } => {
-                    output.push_str(&format!("EVENT [{}] {}\n", event_code, message));
+                    output.push_str(&format!(
+                        "EVENT [{}] {:?}: {}\n",
+                        event_code, severity, message
+                    ));
+                }
+                TelemetryPayload::Diagnostics {
+                    specific_energy,
+                    pointing_error,
+                    position_uncertainty,
+                } => {
+                    output.push_str(&format!(
+                        "DIAG energy={:.1}J/kg pointing={:.2}° nav_sigma={:.1}m\n",
+                        specific_energy,
+                        pointing_error.to_degrees(),
+                        position_uncertainty
+                    ));
+                }
+                TelemetryPayload::Attitude {
+                    quaternion,
+                    angular_velocity,
+                } => {
+                    output.push_str(&format!(
+                        "ATT q=[{:.4}, {:.4}, {:.4}, {:.4}] rate=[{:.4}, {:.4}, {:.4}]rad/s\n",
+                        quaternion[0],
+                        quaternion[1],
+                        quaternion[2],
+                        quaternion[3],
+                        angular_velocity[0],
+                        angular_velocity[1],
+                        angular_velocity[2]
+                    ));
+                }
+                TelemetryPayload::Target { position, velocity } => {
+                    output.push_str(&format!(
+                        "TARGET pos=[{:.0}, {:.0}, {:.0}]m vel=[{:.1}, {:.1}, {:.1}]m/s\n",
+                        position[0],
+                        position[1],
+                        position[2],
+                        velocity[0],
+                        velocity[1],
+                        velocity[2]
+                    ));
                 }
             }
         }
 
         output
     }
+
+    /// Exportiert Navigationsdaten als CSV (eine Zeile pro Navigation-Paket)
+    ///
+    /// Andere Payload-Typen werden übersprungen; Werte im Bezugssystem des
+    /// jeweiligen Pakets, das in der Spalte `frame` steht (ECI/MCI).
+    pub fn export_navigation_csv(&self) -> String {
+        let mut output = String::from("timestamp,frame,x,y,z,vx,vy,vz\n");
+
+        for packet in &self.packets {
+            if let TelemetryPayload::Navigation {
+                position,
+                velocity,
+                frame,
+            } = &packet.payload
+            {
+                output.push_str(&format!(
+                    "{},{},{},{},{},{},{},{}\n",
+                    packet.timestamp,
+                    frame.label(),
+                    position[0],
+                    position[1],
+                    position[2],
+                    velocity[0],
+                    velocity[1],
+                    velocity[2]
+                ));
+            }
+        }
+
+        output
+    }
+
+    /// Exportiert alle Pakete als JSON-Array (maschinenlesbar, z.B. zum Plotten)
+    ///
+    /// Floats werden verlustfrei geschrieben und lassen sich exakt zurücklesen.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.packets).expect("Telemetrie-Pakete sind immer serialisierbar")
+    }
+
+    /// Schreibt alle Pakete als Binärdatei (je Paket: u32-Länge LE + `to_bytes`)
+    pub fn write_binary<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        for packet in &self.packets {
+            let bytes = packet.to_bytes();
+            writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+            writer.write_all(&bytes)?;
+        }
+        writer.flush()
+    }
+
+    /// Delta-kodiert alle Navigationspakete für den bandbreitenbegrenzten Downlink
+    ///
+    /// Format: `TELEMETRY_FORMAT_VERSION`, u32-Anzahl LE, dann je Paket ein
+    /// Datensatz. Schlüsseldatensatz: `NAV_KEYFRAME`, Zeitstempel u64,
+    /// Paket-ID u32, Subsystem, Bezugssystem, 6 × f64. Delta-Datensatz:
+    /// `NAV_DELTA`, Δt u32 [ms], ΔID u16, Subsystem, 6 × f32 als Differenz zum
+    /// rekonstruierten Vorgänger (kein Fehleraufbau, Bezugssystem unverändert).
+    /// Andere Pakettypen werden nicht übertragen.
+    pub fn compress_navigation(&self) -> Vec<u8> {
+        let navigation: Vec<_> = self
+            .packets
+            .iter()
+            .filter_map(|packet| match packet.payload {
+                TelemetryPayload::Navigation {
+                    position,
+                    velocity,
+                    frame,
+                } => Some((packet, position, velocity, frame)),
+                _ => None,
+            })
+            .collect();
+
+        let mut bytes = vec![TELEMETRY_FORMAT_VERSION];
+        bytes.extend_from_slice(&(navigation.len() as u32).to_le_bytes());
+
+        let mut previous: Option<(u64, u32, ReferenceFrame, [f64; 6])> = None;
+        for (packet, position, velocity, frame) in navigation {
+            let mut values = [0.0; 6];
+            values[..3].copy_from_slice(&position);
+            values[3..].copy_from_slice(&velocity);
+
+            let delta = previous.and_then(|(timestamp, packet_id, previous_frame, reference)| {
+                if previous_frame != frame {
+                    return None;
+                }
+                let dt = u32::try_from(packet.timestamp.checked_sub(timestamp)?).ok()?;
+                let did = u16::try_from(packet.packet_id.checked_sub(packet_id)?).ok()?;
+                let mut diffs = [0.0f32; 6];
+                let mut reconstructed = reference;
+                for i in 0..6 {
+                    let diff = values[i] - reference[i];
+                    if !diff.is_finite() || diff.abs() > NAV_MAX_DELTA {
+                        return None;
+                    }
+                    diffs[i] = diff as f32;
+                    reconstructed[i] += diffs[i] as f64;
+                }
+                Some((dt, did, diffs, reconstructed))
+            });
+
+            let reconstructed = match delta {
+                Some((dt, did, diffs, reconstructed)) => {
+                    bytes.push(NAV_DELTA);
+                    bytes.extend_from_slice(&dt.to_le_bytes());
+                    bytes.extend_from_slice(&did.to_le_bytes());
+                    bytes.push(packet.subsystem as u8);
+                    for diff in diffs {
+                        bytes.extend_from_slice(&diff.to_le_bytes());
+                    }
+                    reconstructed
+                }
+                None => {
+                    bytes.push(NAV_KEYFRAME);
+                    bytes.extend_from_slice(&packet.timestamp.to_le_bytes());
+                    bytes.extend_from_slice(&packet.packet_id.to_le_bytes());
+                    bytes.push(packet.subsystem as u8);
+                    bytes.push(frame as u8);
+                    for v in values {
+                        bytes.extend_from_slice(&v.to_le_bytes());
+                    }
+                    values
+                }
+            };
+            previous = Some((packet.timestamp, packet.packet_id, frame, reconstructed));
+        }
+
+        bytes
+    }
+
+    /// Liest eine mit `write_binary` geschriebene Datei
+    pub fn read_binary<P: AsRef<Path>>(path: P) -> io::Result<Vec<TelemetryPacket>> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut packets = Vec::new();
+        let mut len_bytes = [0u8; 4];
+
+        loop {
+            match reader.read_exact(&mut len_bytes) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => break,
+                Err(err) => return Err(err),
+            }
+            let mut bytes = vec![0u8; u32::from_le_bytes(len_bytes) as usize];
+            reader.read_exact(&mut bytes)?;
+            let packet = TelemetryPacket::from_bytes(&bytes).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "Ungültiges Telemetrie-Paket")
+            })?;
+            packets.push(packet);
+        }
+
+        Ok(packets)
+    }
 }
 
 impl Default for TelemetryLogger {
@@ -287,9 +1157,267 @@ impl Default for TelemetryLogger {
     }
 }
 
+/// Ziel für Status- und Phasenmeldungen der Mission
+///
+/// Ersetzt direkte `println!`-Aufrufe, damit die Bibliothek still oder
+/// umgeleitet betrieben werden kann.
+pub trait MissionLogger: Send {
+    fn log(&mut self, message: &str);
+}
+
+/// Gibt Meldungen auf stdout aus
+#[derive(Debug, Default)]
+pub struct StdoutLogger;
+
+impl MissionLogger for StdoutLogger {
+    fn log(&mut self, message: &str) {
+        println!("{}", message);
+    }
+}
+
+/// Verwirft alle Meldungen
+#[derive(Debug, Default)]
+pub struct NullLogger;
+
+impl MissionLogger for NullLogger {
+    fn log(&mut self, _message: &str) {}
+}
+
+/// Sammelt Meldungen im Speicher (z.B. für Tests)
+#[derive(Debug, Clone, Default)]
+pub struct CaptureLogger {
+    messages: Arc<Mutex<Vec<String>>>,
+}
+
+impl CaptureLogger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Kopie aller bisher gesammelten Meldungen
+    pub fn messages(&self) -> Vec<String> {
+        self.messages.lock().unwrap().clone()
+    }
+}
+
+impl MissionLogger for CaptureLogger {
+    fn log(&mut self, message: &str) {
+        self.messages.lock().unwrap().push(message.to_string());
+    }
+}
+
+/// Art eines Meilensteins im Missionsablauf
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum MilestoneKind {
+    /// Missionsbeginn
+    Liftoff,
+    /// Wechsel der Guidance-Phase
+    PhaseChange,
+    /// Triebwerk gezündet
+    BurnStart,
+    /// Triebwerk abgeschaltet
+    BurnStop,
+    /// Ausgebrannte Stufe abgetrennt
+    StageSeparation,
+    /// Oberflächenkontakt mit dem Zielkörper
+    Touchdown,
+    /// Missionsabbruch
+    Abort,
+    /// Missionsende
+    MissionEnd,
+}
+
+/// Einzelner Meilenstein mit Missionszeit
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Milestone {
+    /// Missionszeit [s]
+    pub time: f64,
+    pub kind: MilestoneKind,
+    pub description: String,
+}
+
+/// Kompaktes Missionsprotokoll der wichtigsten Ereignisse
+///
+/// Unabhängig von der hochfrequenten Telemetrie; Brennphasen werden aus dem
+/// Triebwerkszustand je Schritt abgeleitet.
+#[derive(Debug, Clone, Default)]
+pub struct Timeline {
+    milestones: Vec<Milestone>,
+    engine_on: bool,
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trägt einen Meilenstein ein
+    pub fn record(&mut self, time: f64, kind: MilestoneKind, description: &str) {
+        self.milestones.push(Milestone {
+            time,
+            kind,
+            description: description.to_string(),
+        });
+    }
+
+    /// Meldet den Triebwerkszustand; Zünden und Abschalten werden eingetragen
+    pub fn record_engine(&mut self, time: f64, burning: bool, description: &str) {
+        if burning != self.engine_on {
+            let kind = if burning {
+                MilestoneKind::BurnStart
+            } else {
+                MilestoneKind::BurnStop
+            };
+            self.record(time, kind, description);
+            self.engine_on = burning;
+        }
+    }
+
+    /// Alle Meilensteine in zeitlicher Reihenfolge
+    pub fn milestones(&self) -> &[Milestone] {
+        &self.milestones
+    }
+
+    /// Menschenlesbare Darstellung, eine Zeile pro Meilenstein
+    pub fn export(&self) -> String {
+        let mut output = String::from("=== MISSION TIMELINE ===\n");
+        for milestone in &self.milestones {
+            output.push_str(&format!(
+                "T+{:>9.0}s  {:<12} {}\n",
+                milestone.time,
+                format!("{:?}", milestone.kind),
+                milestone.description
+            ));
+        }
+        output
+    }
+}
+
+/// Empfangenes Paket am Boden
+#[derive(Debug, Clone)]
+pub struct ReceivedPacket {
+    pub packet: TelemetryPacket,
+    /// Empfangszeit [s] seit Beginn der Übertragung
+    pub receive_time: f64,
+}
+
+/// Simulierter Downlink mit Paketverlust und begrenzter Bandbreite
+pub struct Downlink {
+    /// Bandbreite [bit/s]
+    pub bandwidth_bps: f64,
+    /// Verlustwahrscheinlichkeit pro Paket (0.0 - 1.0)
+    pub loss_prob: f64,
+    /// Zufallsgenerator für Paketverlust
+    pub rng: StdRng,
+}
+
+impl Downlink {
+    pub fn new(bandwidth_bps: f64, loss_prob: f64, seed: u64) -> Self {
+        Self {
+            bandwidth_bps,
+            loss_prob,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Überträgt Pakete sequentiell über den Link
+    ///
+    /// Jedes Paket belegt den Link für `bytes * 8 / bandwidth` Sekunden,
+    /// auch wenn es unterwegs verloren geht.
+    pub fn transmit(&mut self, packets: &[TelemetryPacket]) -> Vec<ReceivedPacket> {
+        let mut received = Vec::new();
+        let mut link_time = 0.0;
+
+        for packet in packets {
+            let bits = (packet.to_bytes().len() * 8) as f64;
+            link_time += bits / self.bandwidth_bps;
+
+            if self.rng.gen::<f64>() >= self.loss_prob {
+                received.push(ReceivedPacket {
+                    packet: packet.clone(),
+                    receive_time: link_time,
+                });
+            }
+        }
+
+        received
+    }
+}
+
+/// Dekodiert die Ausgabe von `TelemetryLogger::compress_navigation`
+///
+/// Zeitstempel, IDs und CRC sind exakt, Zustände bis auf die f32-Rundung der Deltas.
+pub fn decompress_navigation(bytes: &[u8]) -> Option<Vec<TelemetryPacket>> {
+    let mut reader = ByteReader::new(bytes);
+    if reader.u8()? != TELEMETRY_FORMAT_VERSION {
+        return None;
+    }
+    let count = u32::from_le_bytes(reader.take()?) as usize;
+    let mut packets: Vec<TelemetryPacket> = Vec::with_capacity(count);
+    let mut values = [0.0; 6];
+    let mut frame = ReferenceFrame::EarthCentered;
+
+    for _ in 0..count {
+        let (timestamp, packet_id, subsystem) = match reader.u8()? {
+            NAV_KEYFRAME => {
+                let header = (
+                    u64::from_le_bytes(reader.take()?),
+                    u32::from_le_bytes(reader.take()?),
+                    SubsystemId::from_u8(reader.u8()?)?,
+                );
+                frame = ReferenceFrame::from_u8(reader.u8()?)?;
+                for v in values.iter_mut() {
+                    *v = f64::from_le_bytes(reader.take()?);
+                }
+                header
+            }
+            NAV_DELTA => {
+                let previous = packets.last()?;
+                // Überlauf nur bei fehlerhaften Daten aus dem Downlink
+                let header = (
+                    previous.timestamp.checked_add(u32::from_le_bytes(reader.take()?) as u64)?,
+                    previous.packet_id.checked_add(u16::from_le_bytes(reader.take()?) as u32)?,
+                    SubsystemId::from_u8(reader.u8()?)?,
+                );
+                for v in values.iter_mut() {
+                    *v += f32::from_le_bytes(reader.take()?) as f64;
+                }
+                header
+            }
+            _ => return None,
+        };
+
+        let payload = TelemetryPayload::Navigation {
+            position: [values[0], values[1], values[2]],
+            velocity: [values[3], values[4], values[5]],
+            frame,
+        };
+        packets.push(TelemetryPacket::with_timestamp(
+            packet_id, subsystem, payload, timestamp,
+        ));
+    }
+
+    Some(packets)
+}
+
+/// Erkennt Lücken in der Paket-ID-Folge (bodenseitig nach verlustbehaftetem Downlink)
+///
+/// Gibt Bereiche fehlender IDs als (erste, letzte) zurück.
+pub fn detect_gaps(packets: &[TelemetryPacket]) -> Vec<(u32, u32)> {
+    let mut ids: Vec<u32> = packets.iter().map(|p| p.packet_id).collect();
+    ids.sort_unstable();
+    ids.dedup();
+
+    ids.windows(2)
+        .filter(|w| w[1] > w[0] + 1)
+        .map(|w| (w[0] + 1, w[1] - 1))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::physics::EARTH_MOON_DISTANCE;
 
     #[test]
     fn test_packet_crc() {
@@ -299,11 +1427,89 @@ mod tests {
             TelemetryPayload::Navigation {
                 position: [1.0, 2.0, 3.0],
                 velocity: [4.0, 5.0, 6.0],
+                frame: ReferenceFrame::EarthCentered,
             },
         );
         assert!(packet.validate());
     }
 
+    #[test]
+    fn test_navigation_delta_compression() {
+        let mut logger = TelemetryLogger::new();
+        for i in 0..100 {
+            let t = i as f64 * 60.0;
+            let angle = t * 1.1e-3;
+            let r = R_EARTH + 200_000.0;
+            logger.set_mission_time(t);
+            logger.log_navigation(
+                [r * angle.cos(), r * angle.sin(), 0.0],
+                [-7_790.0 * angle.sin(), 7_790.0 * angle.cos(), 0.0],
+            );
+            logger.log_status(0, 100.0, 100, 3_000.0);
+        }
+
+        let original: Vec<&TelemetryPacket> = logger
+            .get_packets()
+            .iter()
+            .filter(|p| matches!(p.payload, TelemetryPayload::Navigation { .. }))
+            .collect();
+        let raw: usize = original.iter().map(|p| p.to_bytes().len()).sum();
+        let compressed = logger.compress_navigation();
+        assert!(
+            compressed.len() < raw / 2,
+            "{} statt {} Bytes",
+            compressed.len(),
+            raw
+        );
+
+        let restored = decompress_navigation(&compressed).unwrap();
+        assert_eq!(restored.len(), original.len());
+        for (a, b) in original.iter().zip(&restored) {
+            assert_eq!(
+                (a.timestamp, a.packet_id, a.crc),
+                (b.timestamp, b.packet_id, b.crc)
+            );
+            let (
+                TelemetryPayload::Navigation {
+                    position: p1,
+                    velocity: v1,
+                    ..
+                },
+                TelemetryPayload::Navigation {
+                    position: p2,
+                    velocity: v2,
+                    ..
+                },
+            ) = (&a.payload, &b.payload)
+            else {
+                panic!("Navigationspaket erwartet");
+            };
+            for i in 0..3 {
+                assert!((p1[i] - p2[i]).abs() < 0.1);
+                assert!((v1[i] - v2[i]).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decompress_rejects_overflowing_deltas() {
+        let mut logger = TelemetryLogger::new();
+        logger.set_mission_time(0.0);
+        logger.log_navigation([0.0; 3], [0.0; 3]);
+        logger.set_mission_time(1.0);
+        logger.log_navigation([1.0; 3], [1.0; 3]);
+        let compressed = logger.compress_navigation();
+        assert_eq!(decompress_navigation(&compressed).map(|p| p.len()), Some(2));
+
+        // Schlüsseldatensatz ab Byte 5: Typ, Zeitstempel (6..14), Paket-ID (14..18)
+        let mut timestamp = compressed.clone();
+        timestamp[6..14].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(decompress_navigation(&timestamp).is_none());
+        let mut packet_id = compressed.clone();
+        packet_id[14..18].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(decompress_navigation(&packet_id).is_none());
+    }
+
     #[test]
     fn test_serialization() {
         let packet = TelemetryPacket::new(
@@ -313,17 +1519,451 @@ mod tests {
                 phase: 2,
                 fuel_percent: 75.5,
                 system_health: 100,
+                delta_v_remaining: 3_200.0,
             },
         );
         let bytes = packet.to_bytes();
-        assert!(!bytes.is_empty());
+        assert_eq!(bytes[0], TELEMETRY_FORMAT_VERSION);
+        assert!(TelemetryPacket::from_bytes(&bytes).is_some());
+
+        // Pakete eines älteren Layouts werden nicht fehlinterpretiert
+        let mut old = bytes.clone();
+        old[0] = TELEMETRY_FORMAT_VERSION - 1;
+        assert!(TelemetryPacket::from_bytes(&old).is_none());
+    }
+
+    #[test]
+    fn test_attitude_roundtrip() {
+        let mut logger = TelemetryLogger::new();
+        let quaternion = [0.5f64.sqrt(), 0.0, 0.0, 0.5f64.sqrt()];
+        logger.log_attitude(quaternion, [0.01, -0.02, 0.03]);
+
+        let packet = &logger.get_packets()[0];
+        let decoded = TelemetryPacket::from_bytes(&packet.to_bytes()).unwrap();
+        assert!(decoded.validate());
+        match decoded.payload {
+            TelemetryPayload::Attitude {
+                quaternion: q,
+                angular_velocity,
+            } => {
+                assert_eq!(q, quaternion);
+                assert_eq!(angular_velocity, [0.01, -0.02, 0.03]);
+            }
+            other => panic!("{:?}", other),
+        }
+        assert!(logger.export_summary().contains("ATT q=[0.7071, 0.0000"));
     }
 
     #[test]
     fn test_logger() {
         let mut logger = TelemetryLogger::new();
         logger.log_navigation([1e6, 2e6, 3e6], [100.0, 200.0, 300.0]);
-        logger.log_event(SubsystemId::GNC, 1001, "Engine ignition");
+        logger.log_event(SubsystemId::GNC, 1001, EventSeverity::Info, "Engine ignition");
         assert_eq!(logger.get_packets().len(), 2);
     }
+
+    #[test]
+    fn test_mission_time_stamps() {
+        let mut logger = TelemetryLogger::new();
+        logger.set_mission_time(12.5);
+        logger.log_status(1, 50.0, 100, 1_000.0);
+        logger.set_mission_time(60.0);
+        logger.log_status(1, 49.0, 100, 1_000.0);
+
+        let stamps: Vec<u64> = logger.get_packets().iter().map(|p| p.timestamp).collect();
+        assert_eq!(stamps, vec![12_500, 60_000]);
+        assert!(logger.get_packets()[1].validate());
+    }
+
+    #[test]
+    fn test_binary_file_roundtrip() {
+        let mut logger = TelemetryLogger::new();
+        logger.log_navigation([1.0, 2.0, 3.0], [4.0, 5.0, 6.0]);
+        logger.log_status(3, 42.5, 100, 1_000.0);
+        logger.log_event(
+            SubsystemId::FDIR,
+            2001,
+            EventSeverity::Error,
+            "Fault detected",
+        );
+        logger.log_diagnostics(-3.1e7, 0.02, 12.5);
+
+        let path = std::env::temp_dir().join("azb_telemetry_roundtrip.bin");
+        logger.write_binary(&path).unwrap();
+        let packets = TelemetryLogger::read_binary(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(packets.len(), 4);
+        for (read, written) in packets.iter().zip(logger.get_packets()) {
+            assert_eq!(read.to_bytes(), written.to_bytes());
+        }
+    }
+
+    #[test]
+    fn test_drain_by_priority() {
+        let mut logger = TelemetryLogger::new();
+        for i in 0..5 {
+            logger.log_navigation([i as f64; 3], [0.0; 3]);
+        }
+        logger.log_status(2, 80.0, 100, 1_000.0);
+        logger.log_event(
+            SubsystemId::FDIR,
+            2001,
+            EventSeverity::Critical,
+            "Fault detected",
+        );
+
+        // Platz für das Ereignis, den Status und eine Navigation
+        let sizes: Vec<usize> = logger
+            .get_packets()
+            .iter()
+            .map(|p| p.to_bytes().len())
+            .collect();
+        let budget = sizes[6] + sizes[5] + sizes[0];
+        let drained = logger.drain_by_priority(budget);
+
+        let ids: Vec<u32> = drained.iter().map(|p| p.packet_id).collect();
+        assert_eq!(ids, vec![7, 6, 1]);
+        assert_eq!(drained[0].priority, 5);
+        // Restliche Navigation bleibt in Reihenfolge erhalten
+        let remaining: Vec<u32> = logger.get_packets().iter().map(|p| p.packet_id).collect();
+        assert_eq!(remaining, vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_statistics() {
+        let mut logger = TelemetryLogger::new();
+        logger.log_target([EARTH_MOON_DISTANCE, 0.0, 0.0], [0.0, 1_000.0, 0.0]);
+        logger.log_navigation([R_EARTH + 200_000.0, 0.0, 0.0], [0.0, 7_800.0, 0.0]);
+        logger.log_status(1, 80.0, 100, 1_000.0);
+        logger.log_navigation(
+            [R_EARTH + 200_000.0, 3_000.0, 4_000.0],
+            [10_900.0, 0.0, 0.0],
+        );
+        logger.log_status(2, 35.5, 100, 1_000.0);
+        logger.log_event(SubsystemId::GNC, 1001, EventSeverity::Info, "TLI");
+        logger.log_status(2, 40.0, 100, 1_000.0);
+
+        let stats = logger.statistics();
+        assert_eq!(stats.max_speed, 10_900.0);
+        assert_eq!(stats.min_fuel_percent, 35.5);
+        assert!((stats.total_distance - 5_000.0).abs() < 1e-6);
+        assert!((stats.max_earth_altitude - 200_000.0).abs() < 10.0);
+        assert!(stats.min_lunar_altitude > 3.0e8);
+    }
+
+    #[test]
+    fn test_statistics_follow_target_position() {
+        let position = [EARTH_MOON_DISTANCE, 0.0, 0.0];
+        let mut logger = TelemetryLogger::new();
+        logger.log_navigation(position, [0.0; 3]);
+        assert_eq!(logger.statistics().min_lunar_altitude, f64::INFINITY);
+
+        // Mond weitergewandert: Abstand aus der geloggten, nicht der Nominalposition
+        let moon = [EARTH_MOON_DISTANCE, R_MOON + 50_000.0, 0.0];
+        logger.log_target(moon, [-1_000.0, 0.0, 0.0]);
+        logger.log_navigation(position, [0.0; 3]);
+        let stats = logger.statistics();
+        assert!((stats.min_lunar_altitude - 50_000.0).abs() < 1e-6);
+
+        // Erdhöhe auch aus mondzentrierten Paketen
+        logger.log_navigation_in(ReferenceFrame::MoonCentered, [0.0, 1.0e8, 0.0], [0.0; 3]);
+        let expected = (Vector3::from(moon) + Vector3::new(0.0, 1.0e8, 0.0)).norm() - R_EARTH;
+        assert!((logger.statistics().max_earth_altitude - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_moon_centered_navigation() {
+        let moon = Vector3::new(EARTH_MOON_DISTANCE, 0.0, 0.0);
+        let position = moon + Vector3::new(R_MOON + 15_000.0, 0.0, 0.0);
+        let velocity = Vector3::new(0.0, 1_650.0, 0.0);
+        let mut logger = TelemetryLogger::new();
+        logger.log_target(moon.into(), [0.0; 3]);
+        for frame in [ReferenceFrame::EarthCentered, ReferenceFrame::MoonCentered] {
+            let (p, v) = frame.from_earth_centered(&position, &velocity, &moon, &Vector3::zeros());
+            logger.log_navigation_in(frame, p, v);
+        }
+
+        let magnitudes: Vec<f64> = logger
+            .get_packets()
+            .iter()
+            .filter_map(|packet| match packet.payload {
+                TelemetryPayload::Navigation { position, .. } => {
+                    Some(Vector3::from(position).norm())
+                }
+                _ => None,
+            })
+            .collect();
+        assert!(magnitudes[0] > 3.0e8, "{}", magnitudes[0]);
+        assert!(magnitudes[1] < 2.0e6, "{}", magnitudes[1]);
+        let summary = logger.export_summary();
+        assert!(summary.contains("NAV ECI pos=") && summary.contains("NAV MCI pos="));
+
+        // Mondhöhe aus beiden Systemen identisch, Bezugssystem übersteht Kompression
+        let stats = logger.statistics();
+        assert!((stats.min_lunar_altitude - 15_000.0).abs() < 1e-3);
+        let restored = decompress_navigation(&logger.compress_navigation()).unwrap();
+        let frames: Vec<_> = restored
+            .iter()
+            .filter_map(|packet| match packet.payload {
+                TelemetryPayload::Navigation { frame, .. } => Some(frame),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            frames,
+            vec![ReferenceFrame::EarthCentered, ReferenceFrame::MoonCentered]
+        );
+    }
+
+    #[test]
+    fn test_epoch_timestamps() {
+        // Start am 2026-10-17 12:00:00 UTC
+        let epoch_ms = 1_792_238_400_000;
+        let mut logger = TelemetryLogger::new().with_epoch(epoch_ms);
+        logger.log_navigation([R_EARTH, 0.0, 0.0], [0.0, 7_800.0, 0.0]);
+        logger.set_mission_time(10.0);
+        logger.log_navigation([R_EARTH, 78_000.0, 0.0], [0.0, 7_800.0, 0.0]);
+
+        let packets = logger.get_packets();
+        assert_eq!(packets[0].timestamp, epoch_ms);
+        assert_eq!(packets[1].timestamp, epoch_ms + 10_000);
+        assert_eq!(logger.replay_trajectory()[1].time, 10.0);
+    }
+
+    #[test]
+    fn test_replay_trajectory() {
+        let mut logger = TelemetryLogger::new();
+        logger.set_mission_time(0.0);
+        logger.log_navigation([R_EARTH, 0.0, 0.0], [0.0, 7_800.0, 0.0]);
+        logger.log_status(1, 80.0, 100, 1_000.0);
+        logger.set_mission_time(60.0);
+        logger.log_navigation([R_EARTH, 468_000.0, 0.0], [-500.0, 7_780.0, 0.0]);
+
+        let mut replayed = 0;
+        logger.replay(|_| replayed += 1);
+        assert_eq!(replayed, 3);
+
+        let trajectory = logger.replay_trajectory();
+        assert_eq!(trajectory.len(), 2);
+        assert_eq!(trajectory[0].position, Vector3::new(R_EARTH, 0.0, 0.0));
+        assert_eq!(
+            trajectory[1].position,
+            Vector3::new(R_EARTH, 468_000.0, 0.0)
+        );
+        assert_eq!(trajectory[1].velocity, Vector3::new(-500.0, 7_780.0, 0.0));
+        assert_eq!(trajectory[1].time, 60.0);
+    }
+
+    #[test]
+    fn test_replay_trajectory_earth_centered() {
+        let moon = Vector3::new(EARTH_MOON_DISTANCE, 0.0, 0.0);
+        let moon_velocity = Vector3::new(0.0, 1_000.0, 0.0);
+        let position = moon + Vector3::new(R_MOON + 15_000.0, 0.0, 0.0);
+        let velocity = moon_velocity + Vector3::new(0.0, 1_650.0, 0.0);
+        let mut logger = TelemetryLogger::new();
+
+        // Mondzentriertes Paket ohne bekannten Zielkörper-Zustand: ausgelassen
+        let frame = ReferenceFrame::MoonCentered;
+        let (p, v) = frame.from_earth_centered(&position, &velocity, &moon, &moon_velocity);
+        logger.log_navigation_in(frame, p, v);
+        assert!(logger.replay_trajectory().is_empty());
+
+        logger.log_target(moon.into(), moon_velocity.into());
+        logger.log_navigation_in(frame, p, v);
+        logger.log_navigation(position.into(), velocity.into());
+
+        let trajectory = logger.replay_trajectory();
+        assert_eq!(trajectory.len(), 2);
+        for state in &trajectory {
+            assert!((state.position - position).norm() < 1e-6);
+            assert!((state.velocity - velocity).norm() < 1e-9);
+        }
+
+        // Zielkörper-Zustand übersteht die Binärkodierung
+        let target = logger.get_packets()[1].clone();
+        let decoded = TelemetryPacket::from_bytes(&target.to_bytes()).unwrap();
+        assert!(matches!(decoded.payload, TelemetryPayload::Target { .. }));
+        assert!(logger.export_summary().contains("TARGET pos="));
+    }
+
+    #[test]
+    fn test_timeline_engine_edges() {
+        let mut timeline = Timeline::new();
+        timeline.record(0.0, MilestoneKind::Liftoff, "Mission start");
+        for (time, burning) in [(1.0, true), (2.0, true), (3.0, false), (4.0, false)] {
+            timeline.record_engine(time, burning, "TLI");
+        }
+
+        let kinds: Vec<MilestoneKind> = timeline.milestones().iter().map(|m| m.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                MilestoneKind::Liftoff,
+                MilestoneKind::BurnStart,
+                MilestoneKind::BurnStop
+            ]
+        );
+        assert!(timeline.export().contains("BurnStop"));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let mut logger = TelemetryLogger::new();
+        logger.log_navigation([0.1, 2e6, -3.3], [100.25, 0.0, 1.0 / 3.0]);
+        logger.log_status(2, 75.5, 100, 1_000.0);
+        logger.log_event(SubsystemId::GNC, 1001, EventSeverity::Info, "Engine ignition");
+
+        let json: serde_json::Value = serde_json::from_str(&logger.to_json()).unwrap();
+        let packets = json.as_array().unwrap();
+        assert_eq!(packets.len(), 3);
+
+        // Typisierte Payload-Felder, Floats exakt
+        assert_eq!(packets[0]["payload"]["type"], "Navigation");
+        assert_eq!(packets[0]["payload"]["velocity"][2].as_f64(), Some(1.0 / 3.0));
+        assert_eq!(packets[1]["payload"]["phase"], 2);
+        assert_eq!(packets[2]["subsystem"], "GNC");
+    }
+
+    #[test]
+    fn test_navigation_csv() {
+        let mut logger = TelemetryLogger::new();
+        logger.log_navigation([1.0, 2.0, 3.0], [4.0, 5.0, 6.0]);
+        logger.log_status(1, 50.0, 100, 1_000.0);
+        logger.log_navigation([7.0, 8.0, 9.0], [10.0, 11.0, 12.0]);
+
+        let csv = logger.export_navigation_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3); // Header + 2 Datenzeilen
+        assert_eq!(lines[0], "timestamp,frame,x,y,z,vx,vy,vz");
+        assert!(lines[2].ends_with(",ECI,7,8,9,10,11,12"));
+
+        let moon = [EARTH_MOON_DISTANCE, 0.0, 0.0];
+        logger.log_navigation_in(ReferenceFrame::MoonCentered, moon, [0.0; 3]);
+        let csv = logger.export_navigation_csv();
+        assert!(csv.lines().last().unwrap().contains(",MCI,"));
+    }
+
+    #[test]
+    fn test_ring_buffer() {
+        let mut logger = TelemetryLogger::with_capacity(10);
+        for i in 0..15 {
+            logger.log_status(0, i as f32, 100, 1_000.0);
+        }
+
+        let packets = logger.get_packets();
+        assert_eq!(packets.len(), 10);
+        assert_eq!(logger.dropped_count(), 5);
+        // Die letzten 10 Pakete bleiben erhalten (IDs 6..=15)
+        assert_eq!(packets[0].packet_id, 6);
+        assert_eq!(packets[9].packet_id, 15);
+    }
+
+    #[test]
+    fn test_packet_filters() {
+        let mut logger = TelemetryLogger::new();
+        logger.log_navigation([0.0; 3], [0.0; 3]);
+        logger.log_status(1, 90.0, 100, 1_000.0);
+        logger.log_event(SubsystemId::Propulsion, 2001, EventSeverity::Info, "Burn start");
+        logger.log_navigation([1.0; 3], [1.0; 3]);
+
+        // Zeitstempel deterministisch setzen
+        for (i, packet) in logger.packets.iter_mut().enumerate() {
+            packet.timestamp = 1000 * (i as u64 + 1);
+        }
+
+        let gnc = logger.packets_for(SubsystemId::GNC);
+        assert_eq!(gnc.len(), 2);
+        assert!(gnc.iter().all(|p| p.subsystem == SubsystemId::GNC));
+        assert_eq!(logger.packets_for(SubsystemId::Thermal).len(), 0);
+
+        let window = logger.packets_between(2000, 3000);
+        let ids: Vec<u32> = window.iter().map(|p| p.packet_id).collect();
+        assert_eq!(ids, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_downlink_loss() {
+        let mut logger = TelemetryLogger::new();
+        for _ in 0..20 {
+            logger.log_navigation([1.0; 3], [2.0; 3]);
+        }
+
+        let mut perfect = Downlink::new(9600.0, 0.0, 42);
+        let received = perfect.transmit(logger.get_packets());
+        assert_eq!(received.len(), 20);
+        // Verzögerung wächst mit jedem Paket
+        assert!(received.windows(2).all(|w| w[1].receive_time > w[0].receive_time));
+
+        let mut dead = Downlink::new(9600.0, 1.0, 42);
+        assert!(dead.transmit(logger.get_packets()).is_empty());
+    }
+
+    #[test]
+    fn test_detect_gaps() {
+        let packets: Vec<TelemetryPacket> = [1, 2, 5, 6]
+            .iter()
+            .map(|&id| {
+                TelemetryPacket::new(
+                    id,
+                    SubsystemId::GNC,
+                    TelemetryPayload::Navigation {
+                        position: [0.0; 3],
+                        velocity: [0.0; 3],
+                        frame: ReferenceFrame::EarthCentered,
+                    },
+                )
+            })
+            .collect();
+
+        assert_eq!(detect_gaps(&packets), vec![(3, 4)]);
+        assert!(detect_gaps(&packets[..2]).is_empty());
+    }
+
+    #[test]
+    fn test_ccsds_framing() {
+        let packet = TelemetryPacket::new(
+            7,
+            SubsystemId::Propulsion,
+            TelemetryPayload::Event {
+                event_code: 3001,
+                severity: EventSeverity::Info,
+                message: "Main engine cutoff".to_string(),
+            },
+        );
+        let payload_len = packet.to_bytes().len();
+        let frame = packet.to_ccsds();
+
+        assert_eq!(frame.len(), CCSDS_PRIMARY_HEADER_LEN + payload_len);
+        // Data Length = Länge Datenfeld - 1
+        let data_length = u16::from_be_bytes([frame[4], frame[5]]) as usize;
+        assert_eq!(data_length, payload_len - 1);
+        // APID = Subsystem
+        assert_eq!(u16::from_be_bytes([frame[0], frame[1]]) & 0x07FF, 3);
+
+        let decoded = TelemetryPacket::from_ccsds(&frame).unwrap();
+        assert_eq!(decoded.packet_id, 7);
+        assert_eq!(decoded.subsystem, SubsystemId::Propulsion);
+        assert!(decoded.validate());
+        assert!(TelemetryPacket::from_ccsds(&frame[..10]).is_none());
+    }
+
+    #[test]
+    fn test_event_severity_roundtrip() {
+        let mut logger = TelemetryLogger::new();
+        logger.log_event(SubsystemId::FDIR, 2001, EventSeverity::Critical, "Max recovery exceeded");
+
+        let bytes = logger.get_packets()[0].to_bytes();
+        let decoded = TelemetryPacket::from_bytes(&bytes).unwrap();
+        match decoded.payload {
+            TelemetryPayload::Event {
+                severity, message, ..
+            } => {
+                assert_eq!(severity, EventSeverity::Critical);
+                assert_eq!(message, "Max recovery exceeded");
+            }
+            _ => panic!("Event-Payload erwartet"),
+        }
+        assert!(logger.export_summary().contains("Critical"));
+    }
 }
@@ -0,0 +1,97 @@
+//! Regressionstest: vollständiger `--test`-Lauf mit festem Seed gegen
+//! gespeicherte Referenzwerte (schützt Integrator und Guidance vor
+//! unbemerkten Änderungen der Dynamik)
+//!
+//! Bei beabsichtigten Änderungen der Dynamik Referenzwerte neu erzeugen.
+
+use azb_raumschiff::simulation::{MoonMissionSim, SimConfig, StepOutcome};
+use nalgebra::Vector3;
+
+/// Referenz: Endposition [m]
+const GOLDEN_POSITION: [f64; 3] = [9_269_760.242_819_466, -18_251_304.002_936_274, 0.0];
+/// Referenz: Endgeschwindigkeit [m/s]
+const GOLDEN_VELOCITY: [f64; 3] = [5_410.725_921_527_874, -2_763.051_886_168_895, 0.0];
+/// Referenz: verbrauchter Treibstoff [kg]
+const GOLDEN_FUEL_USED: f64 = 23_340.171_097_050_77;
+/// Referenz: Kalman-Schätzung der Endposition [m] (abhängig vom Seed)
+const GOLDEN_ESTIMATE: [f64; 3] = [
+    9_269_735.472_917_693,
+    -18_251_286.853_609_66,
+    20.988_295_048_711_78,
+];
+
+/// Konfiguration wie `cargo run -- --test`, mit festem Seed
+fn test_config() -> SimConfig {
+    SimConfig {
+        dt: 1.0,
+        max_time: 3600.0,
+        telemetry_interval: 60.0,
+        quiet: true,
+        seed: 7,
+        ..Default::default()
+    }
+}
+
+fn assert_close(actual: &Vector3<f64>, expected: [f64; 3], tolerance: f64, what: &str) {
+    let expected = Vector3::from(expected);
+    assert!(
+        (actual - expected).norm() < tolerance,
+        "{} weicht ab: {:?} statt {:?}",
+        what,
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn test_golden_trajectory() {
+    let mut sim = MoonMissionSim::new(test_config());
+    let result = sim.run();
+
+    assert_eq!(result.outcome, StepOutcome::TimeLimit);
+    assert_eq!(result.mission_time, 3600.0);
+    assert_close(
+        &result.final_state.position,
+        GOLDEN_POSITION,
+        1e-3,
+        "Position",
+    );
+    assert_close(
+        &result.final_state.velocity,
+        GOLDEN_VELOCITY,
+        1e-6,
+        "Geschwindigkeit",
+    );
+    assert!((result.fuel_used - GOLDEN_FUEL_USED).abs() < 1e-6);
+    assert_close(
+        &sim.kalman.estimated_position(),
+        GOLDEN_ESTIMATE,
+        1e-3,
+        "Kalman-Schätzung",
+    );
+
+    // Telemetrie trägt Missionszeit statt Systemzeit
+    let last = result.telemetry.get_packets().last().unwrap();
+    assert_eq!(last.timestamp, 3_600_000);
+}
+
+#[test]
+fn test_identical_runs() {
+    let mut first = MoonMissionSim::new(test_config());
+    let mut second = MoonMissionSim::new(test_config());
+    let (a, b) = (first.run(), second.run());
+
+    assert_eq!(a.final_state.position, b.final_state.position);
+    assert_eq!(
+        first.kalman.estimated_position(),
+        second.kalman.estimated_position()
+    );
+    let stamps = |r: &azb_raumschiff::simulation::SimResult| {
+        r.telemetry
+            .get_packets()
+            .iter()
+            .map(|p| p.timestamp)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(stamps(&a), stamps(&b));
+}